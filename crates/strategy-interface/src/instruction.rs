@@ -0,0 +1,77 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::instruction::{AccountMeta, Instruction};
+
+/// `global:deposit` sighash.
+pub const DEPOSIT_DISCRIMINATOR: [u8; 8] = [242, 35, 198, 137, 82, 225, 242, 182];
+/// `global:withdraw` sighash.
+pub const WITHDRAW_DISCRIMINATOR: [u8; 8] = [183, 18, 70, 156, 148, 109, 161, 34];
+/// `global:total_assets` sighash.
+pub const TOTAL_ASSETS_DISCRIMINATOR: [u8; 8] = [21, 211, 56, 201, 244, 191, 106, 230];
+
+/// Instructions every strategy program implements. The first account is always the
+/// vault's `Strategy` PDA, which identifies the vault and signs deposits and withdrawals;
+/// accounts after the listed ones are strategy-specific and forwarded as given.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum StrategyInstruction {
+    /// Invests `amount` of assets already transferred to the strategy asset account.
+    ///
+    /// Accounts: `[strategy (signer), strategy_asset_account (writable), asset_mint,
+    /// asset_token_program, ..]`.
+    Deposit { amount: u64 },
+    /// Sends `amount` of assets from the strategy back to the vault's asset vault.
+    ///
+    /// Accounts: `[strategy (signer), strategy_asset_account (writable),
+    /// asset_vault (writable), asset_mint, asset_token_program, ..]`.
+    Withdraw { amount: u64 },
+    /// Returns, as a little-endian `u64` in the return data, the assets the strategy
+    /// currently holds for the vault, gains and losses included.
+    ///
+    /// Accounts: `[strategy, strategy_asset_account, ..]`.
+    TotalAssets,
+}
+
+impl StrategyInstruction {
+    pub fn pack(&self) -> Vec<u8> {
+        match self {
+            Self::Deposit { amount } => {
+                [&DEPOSIT_DISCRIMINATOR[..], &amount.to_le_bytes()].concat()
+            }
+            Self::Withdraw { amount } => {
+                [&WITHDRAW_DISCRIMINATOR[..], &amount.to_le_bytes()].concat()
+            }
+            Self::TotalAssets => TOTAL_ASSETS_DISCRIMINATOR.to_vec(),
+        }
+    }
+
+    pub fn unpack(data: &[u8]) -> Result<Self> {
+        if data.len() < 8 {
+            return Err(ProgramError::InvalidInstructionData.into());
+        }
+        let (discriminator, rest) = data.split_at(8);
+        let amount = || -> Result<u64> {
+            rest.get(..8)
+                .and_then(|bytes| bytes.try_into().ok())
+                .map(u64::from_le_bytes)
+                .ok_or_else(|| ProgramError::InvalidInstructionData.into())
+        };
+        match discriminator {
+            d if d == DEPOSIT_DISCRIMINATOR => Ok(Self::Deposit { amount: amount()? }),
+            d if d == WITHDRAW_DISCRIMINATOR => Ok(Self::Withdraw { amount: amount()? }),
+            d if d == TOTAL_ASSETS_DISCRIMINATOR => Ok(Self::TotalAssets),
+            _ => Err(ProgramError::InvalidInstructionData.into()),
+        }
+    }
+
+    /// Builds the instruction for `strategy_program` with the given account metas.
+    pub fn to_instruction(
+        &self,
+        strategy_program: Pubkey,
+        accounts: Vec<AccountMeta>,
+    ) -> Instruction {
+        Instruction {
+            program_id: strategy_program,
+            accounts,
+            data: self.pack(),
+        }
+    }
+}
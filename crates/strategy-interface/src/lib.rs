@@ -0,0 +1,10 @@
+//! Instruction interface between the tokenized vault and its strategy programs.
+//!
+//! A strategy program exposes `deposit(amount)`, `withdraw(amount)` and `total_assets()`,
+//! using Anchor's `global:<name>` discriminators, so that an Anchor program with these
+//! instruction names implements the interface without extra glue.
+
+pub mod instruction;
+pub mod onchain;
+
+pub use instruction::*;
@@ -0,0 +1,70 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::instruction::AccountMeta;
+use anchor_lang::solana_program::program::{get_return_data, invoke_signed};
+
+use crate::StrategyInstruction;
+
+/// Invokes `instruction` on a strategy program, signed with `signer_seeds`.
+///
+/// `accounts` are the interface accounts in order, paired with their writable flag; the
+/// first one is passed as signer. `remaining_accounts` are forwarded with their own flags.
+pub fn invoke<'info>(
+    strategy_program: &AccountInfo<'info>,
+    instruction: &StrategyInstruction,
+    accounts: &[(AccountInfo<'info>, bool)],
+    remaining_accounts: &[AccountInfo<'info>],
+    signer_seeds: &[&[&[u8]]],
+) -> Result<()> {
+    let mut metas = Vec::with_capacity(accounts.len() + remaining_accounts.len());
+    for (i, (account, writable)) in accounts.iter().enumerate() {
+        let is_signer = i == 0 && !signer_seeds.is_empty();
+        metas.push(if *writable {
+            AccountMeta::new(account.key(), is_signer)
+        } else {
+            AccountMeta::new_readonly(account.key(), is_signer)
+        });
+    }
+    metas.extend(remaining_accounts.iter().map(|account| {
+        if account.is_writable {
+            AccountMeta::new(account.key(), account.is_signer)
+        } else {
+            AccountMeta::new_readonly(account.key(), account.is_signer)
+        }
+    }));
+
+    let mut infos: Vec<AccountInfo<'info>> = accounts
+        .iter()
+        .map(|(account, _)| account.clone())
+        .collect();
+    infos.extend_from_slice(remaining_accounts);
+    infos.push(strategy_program.clone());
+    invoke_signed(
+        &instruction.to_instruction(strategy_program.key(), metas),
+        &infos,
+        signer_seeds,
+    )?;
+    Ok(())
+}
+
+/// Calls `total_assets` on a strategy program and reads the `u64` it returns.
+pub fn total_assets<'info>(
+    strategy_program: &AccountInfo<'info>,
+    accounts: &[(AccountInfo<'info>, bool)],
+    remaining_accounts: &[AccountInfo<'info>],
+) -> Result<u64> {
+    invoke(
+        strategy_program,
+        &StrategyInstruction::TotalAssets,
+        accounts,
+        remaining_accounts,
+        &[],
+    )?;
+    match get_return_data() {
+        Some((program_id, data)) if program_id == strategy_program.key() => data
+            .get(..8)
+            .and_then(|bytes| bytes.try_into().ok())
+            .map(u64::from_le_bytes)
+            .ok_or_else(|| ProgramError::InvalidAccountData.into()),
+        _ => Err(ProgramError::InvalidAccountData.into()),
+    }
+}
@@ -0,0 +1,160 @@
+//! Strategy implementing the vault's strategy interface for local testing.
+//!
+//! Assets are kept in a token account owned by the `["authority", strategy]` PDA, where
+//! `strategy` is the vault's `Strategy` PDA, and `total_assets` reports its balance. Yield
+//! and losses are simulated with `simulate_gain` and `simulate_loss`. The authority PDA is
+//! passed after the interface accounts (as remaining accounts of the vault instructions).
+
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{
+    burn_checked, transfer_checked, BurnChecked, Mint, TokenAccount, TokenInterface,
+    TransferChecked,
+};
+
+declare_id!("72xESQSZ8x3yNGHcDpHwaGfDqfd1AutJ1WAt7RcQQvcu");
+
+pub const AUTHORITY_SEED: &str = "authority";
+
+#[program]
+pub mod mock_strategy {
+    use super::*;
+
+    pub fn deposit(_ctx: Context<Deposit>, amount: u64) -> Result<()> {
+        msg!("Deposited {} assets", amount);
+        Ok(())
+    }
+
+    pub fn withdraw(ctx: Context<Withdraw>, amount: u64) -> Result<()> {
+        let strategy = ctx.accounts.strategy.key();
+        let seeds = &[
+            AUTHORITY_SEED.as_bytes(),
+            strategy.as_ref(),
+            &[ctx.bumps.authority],
+        ];
+        transfer_checked(
+            CpiContext::new_with_signer(
+                ctx.accounts.asset_token_program.to_account_info(),
+                TransferChecked {
+                    from: ctx.accounts.strategy_asset_account.to_account_info(),
+                    to: ctx.accounts.asset_vault.to_account_info(),
+                    mint: ctx.accounts.asset_mint.to_account_info(),
+                    authority: ctx.accounts.authority.to_account_info(),
+                },
+                &[&seeds[..]],
+            ),
+            amount,
+            ctx.accounts.asset_mint.decimals,
+        )
+    }
+
+    pub fn total_assets(ctx: Context<TotalAssets>) -> Result<u64> {
+        Ok(ctx.accounts.strategy_asset_account.amount)
+    }
+
+    /// Transfers `amount` from the funder into the strategy, as if it were earned yield.
+    pub fn simulate_gain(ctx: Context<SimulateGain>, amount: u64) -> Result<()> {
+        transfer_checked(
+            CpiContext::new(
+                ctx.accounts.asset_token_program.to_account_info(),
+                TransferChecked {
+                    from: ctx.accounts.funder_asset_account.to_account_info(),
+                    to: ctx.accounts.strategy_asset_account.to_account_info(),
+                    mint: ctx.accounts.asset_mint.to_account_info(),
+                    authority: ctx.accounts.funder.to_account_info(),
+                },
+            ),
+            amount,
+            ctx.accounts.asset_mint.decimals,
+        )
+    }
+
+    /// Burns `amount` of the strategy's assets, as if they were lost.
+    pub fn simulate_loss(ctx: Context<SimulateLoss>, amount: u64) -> Result<()> {
+        let strategy = ctx.accounts.strategy.key();
+        let seeds = &[
+            AUTHORITY_SEED.as_bytes(),
+            strategy.as_ref(),
+            &[ctx.bumps.authority],
+        ];
+        burn_checked(
+            CpiContext::new_with_signer(
+                ctx.accounts.asset_token_program.to_account_info(),
+                BurnChecked {
+                    mint: ctx.accounts.asset_mint.to_account_info(),
+                    from: ctx.accounts.strategy_asset_account.to_account_info(),
+                    authority: ctx.accounts.authority.to_account_info(),
+                },
+                &[&seeds[..]],
+            ),
+            amount,
+            ctx.accounts.asset_mint.decimals,
+        )
+    }
+}
+
+#[derive(Accounts)]
+pub struct Deposit<'info> {
+    pub strategy: Signer<'info>,
+    #[account(mut, token::mint = asset_mint, token::authority = authority, token::token_program = asset_token_program)]
+    pub strategy_asset_account: InterfaceAccount<'info, TokenAccount>,
+    pub asset_mint: InterfaceAccount<'info, Mint>,
+    pub asset_token_program: Interface<'info, TokenInterface>,
+    /// CHECK: Owner of the strategy asset account
+    #[account(seeds = [AUTHORITY_SEED.as_bytes(), strategy.key().as_ref()], bump)]
+    pub authority: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct Withdraw<'info> {
+    pub strategy: Signer<'info>,
+    #[account(mut, token::mint = asset_mint, token::authority = authority, token::token_program = asset_token_program)]
+    pub strategy_asset_account: InterfaceAccount<'info, TokenAccount>,
+    #[account(mut, token::mint = asset_mint, token::token_program = asset_token_program)]
+    pub asset_vault: InterfaceAccount<'info, TokenAccount>,
+    pub asset_mint: InterfaceAccount<'info, Mint>,
+    pub asset_token_program: Interface<'info, TokenInterface>,
+    /// CHECK: Owner of the strategy asset account
+    #[account(seeds = [AUTHORITY_SEED.as_bytes(), strategy.key().as_ref()], bump)]
+    pub authority: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct TotalAssets<'info> {
+    /// CHECK: Strategy PDA of the vault
+    pub strategy: UncheckedAccount<'info>,
+    #[account(token::authority = authority)]
+    pub strategy_asset_account: InterfaceAccount<'info, TokenAccount>,
+    /// CHECK: Owner of the strategy asset account
+    #[account(seeds = [AUTHORITY_SEED.as_bytes(), strategy.key().as_ref()], bump)]
+    pub authority: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SimulateGain<'info> {
+    pub funder: Signer<'info>,
+    #[account(mut, token::mint = asset_mint, token::token_program = asset_token_program)]
+    pub funder_asset_account: InterfaceAccount<'info, TokenAccount>,
+    /// CHECK: Strategy PDA of the vault
+    pub strategy: UncheckedAccount<'info>,
+    #[account(mut, token::mint = asset_mint, token::authority = authority, token::token_program = asset_token_program)]
+    pub strategy_asset_account: InterfaceAccount<'info, TokenAccount>,
+    /// CHECK: Owner of the strategy asset account
+    #[account(seeds = [AUTHORITY_SEED.as_bytes(), strategy.key().as_ref()], bump)]
+    pub authority: UncheckedAccount<'info>,
+    pub asset_mint: InterfaceAccount<'info, Mint>,
+    pub asset_token_program: Interface<'info, TokenInterface>,
+}
+
+#[derive(Accounts)]
+pub struct SimulateLoss<'info> {
+    /// CHECK: Strategy PDA of the vault
+    pub strategy: UncheckedAccount<'info>,
+    #[account(mut, token::mint = asset_mint, token::authority = authority, token::token_program = asset_token_program)]
+    pub strategy_asset_account: InterfaceAccount<'info, TokenAccount>,
+    /// CHECK: Owner of the strategy asset account
+    #[account(seeds = [AUTHORITY_SEED.as_bytes(), strategy.key().as_ref()], bump)]
+    pub authority: UncheckedAccount<'info>,
+    #[account(mut)]
+    pub asset_mint: InterfaceAccount<'info, Mint>,
+    pub asset_token_program: Interface<'info, TokenInterface>,
+}
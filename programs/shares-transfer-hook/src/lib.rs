@@ -0,0 +1,178 @@
+//! Whitelist transfer hook of the tokenized vault's shares mint.
+//!
+//! Vaults initialized with `SHARES_TRANSFER_HOOK` point the shares mint's `TransferHook`
+//! extension at this program. Every share transfer requires both the source and destination
+//! owners to hold the vault's `WHITELISTED` permission; accounts owned by the shares mint
+//! (the vault's escrows) are exempt. The hook lives outside the vault so that the vault can
+//! move hooked shares itself: a hook pointing back at the vault would re-enter it.
+
+use anchor_lang::prelude::*;
+use anchor_spl::token_2022::spl_token_2022::{
+    self,
+    extension::{transfer_hook::TransferHookAccount, BaseStateWithExtensions, StateWithExtensions},
+};
+use anchor_spl::token_interface::{Mint, TokenAccount};
+use spl_discriminator::SplDiscriminate;
+use spl_tlv_account_resolution::{
+    account::ExtraAccountMeta, seeds::Seed, state::ExtraAccountMetaList,
+};
+use spl_transfer_hook_interface::instruction::ExecuteInstruction;
+
+declare_id!("62FUtUf67JgPpdxj8GZDzzM5mhysBmHJDunVoxbWuSsF");
+
+pub const EXTRA_ACCOUNT_METAS_SEED: &str = "extra-account-metas";
+
+/// The tokenized vault, owner of the shares mint and of the `Access` accounts.
+pub const VAULT_PROGRAM_ID: Pubkey = pubkey!("6DG8Q5KBjC8UipDajgikmDR6pM8nAtPLzctFpgCUDXbM");
+const ACCESS_SEED: &str = "access";
+const SHARES_MINT_SEED: &str = "shares_mint";
+const WHITELISTED_PERMISSION: u128 = 1 << 1;
+
+/// Offset of `Access::permissions`, after the discriminator and `Access::account`.
+const ACCESS_PERMISSIONS_OFFSET: usize = 8 + 32;
+/// Offset of the owner in a token account.
+const TOKEN_ACCOUNT_OWNER_OFFSET: u8 = 32;
+/// Index of the vault program in the `Execute` accounts.
+const VAULT_PROGRAM_INDEX: u8 = 5;
+
+#[program]
+pub mod shares_transfer_hook {
+    use super::*;
+
+    /// Creates the extra-account-metas list of the shares mint. The list is the same for every
+    /// vault, so anyone can pay for it.
+    pub fn initialize_extra_account_meta_list(
+        ctx: Context<InitializeExtraAccountMetaList>,
+    ) -> Result<()> {
+        ExtraAccountMetaList::init::<ExecuteInstruction>(
+            &mut ctx.accounts.extra_account_meta_list.try_borrow_mut_data()?,
+            &extra_account_metas()?,
+        )?;
+        Ok(())
+    }
+
+    #[instruction(discriminator = ExecuteInstruction::SPL_DISCRIMINATOR_SLICE)]
+    pub fn transfer_hook(ctx: Context<TransferHook>, _amount: u64) -> Result<()> {
+        check_is_transferring(&ctx.accounts.source.to_account_info())?;
+        ctx.accounts
+            .check_whitelisted(&ctx.accounts.source.owner, &ctx.accounts.source_access)?;
+        ctx.accounts.check_whitelisted(
+            &ctx.accounts.destination.owner,
+            &ctx.accounts.destination_access,
+        )?;
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct InitializeExtraAccountMetaList<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    /// CHECK: Initialized with the extra account metas
+    #[account(
+        init,
+        payer = payer,
+        space = ExtraAccountMetaList::size_of(extra_account_metas()?.len())?,
+        seeds = [EXTRA_ACCOUNT_METAS_SEED.as_bytes(), mint.key().as_ref()],
+        bump,
+    )]
+    pub extra_account_meta_list: UncheckedAccount<'info>,
+    #[account(seeds = [SHARES_MINT_SEED.as_bytes()], bump, seeds::program = VAULT_PROGRAM_ID)]
+    pub mint: InterfaceAccount<'info, Mint>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Accounts of the transfer-hook `Execute` instruction, in interface order.
+#[derive(Accounts)]
+pub struct TransferHook<'info> {
+    #[account(token::mint = mint)]
+    pub source: InterfaceAccount<'info, TokenAccount>,
+    #[account(seeds = [SHARES_MINT_SEED.as_bytes()], bump, seeds::program = VAULT_PROGRAM_ID)]
+    pub mint: InterfaceAccount<'info, Mint>,
+    #[account(token::mint = mint)]
+    pub destination: InterfaceAccount<'info, TokenAccount>,
+    /// CHECK: Owner or delegate of the source account, validated by Token-2022
+    pub authority: UncheckedAccount<'info>,
+    /// CHECK: Validated by seeds
+    #[account(seeds = [EXTRA_ACCOUNT_METAS_SEED.as_bytes(), mint.key().as_ref()], bump)]
+    pub extra_account_meta_list: UncheckedAccount<'info>,
+
+    /// CHECK: Validated by address
+    #[account(address = VAULT_PROGRAM_ID)]
+    pub vault_program: UncheckedAccount<'info>,
+    /// CHECK: Access of the source owner, may be uninitialized
+    #[account(seeds = [ACCESS_SEED.as_bytes(), source.owner.as_ref()], bump, seeds::program = VAULT_PROGRAM_ID)]
+    pub source_access: UncheckedAccount<'info>,
+    /// CHECK: Access of the destination owner, may be uninitialized
+    #[account(seeds = [ACCESS_SEED.as_bytes(), destination.owner.as_ref()], bump, seeds::program = VAULT_PROGRAM_ID)]
+    pub destination_access: UncheckedAccount<'info>,
+}
+
+impl TransferHook<'_> {
+    /// Vault-owned accounts (escrows) are exempt; any other holder must be whitelisted.
+    fn check_whitelisted(&self, owner: &Pubkey, access: &AccountInfo) -> Result<()> {
+        if owner == &self.mint.key() {
+            return Ok(());
+        }
+        if access.owner != &VAULT_PROGRAM_ID {
+            return Err(ErrorCode::UnauthorizedPermission.into());
+        }
+        // The seeds pin the account to the vault's `Access` PDA of `owner`
+        let data = access.try_borrow_data()?;
+        let permissions = data
+            .get(ACCESS_PERMISSIONS_OFFSET..ACCESS_PERMISSIONS_OFFSET + 16)
+            .and_then(|bytes| bytes.try_into().ok())
+            .map(u128::from_le_bytes)
+            .ok_or(ErrorCode::UnauthorizedPermission)?;
+        if permissions & WHITELISTED_PERMISSION != WHITELISTED_PERMISSION {
+            return Err(ErrorCode::UnauthorizedPermission.into());
+        }
+        Ok(())
+    }
+}
+
+/// Extra accounts resolved by Token-2022: the vault program and the `Access` PDAs of the
+/// source and destination owners, derived under it.
+fn extra_account_metas() -> Result<Vec<ExtraAccountMeta>> {
+    let access_of = |account_index: u8| {
+        ExtraAccountMeta::new_external_pda_with_seeds(
+            VAULT_PROGRAM_INDEX,
+            &[
+                Seed::Literal {
+                    bytes: ACCESS_SEED.as_bytes().to_vec(),
+                },
+                Seed::AccountData {
+                    account_index,
+                    data_index: TOKEN_ACCOUNT_OWNER_OFFSET,
+                    length: 32,
+                },
+            ],
+            false,
+            false,
+        )
+    };
+    Ok(vec![
+        ExtraAccountMeta::new_with_pubkey(&VAULT_PROGRAM_ID, false, false)?,
+        access_of(0)?,
+        access_of(2)?,
+    ])
+}
+
+/// Fails unless `source` is mid-transfer, i.e. the hook is invoked by Token-2022.
+fn check_is_transferring(source: &AccountInfo) -> Result<()> {
+    let data = source.try_borrow_data()?;
+    let account = StateWithExtensions::<spl_token_2022::state::Account>::unpack(&data)?;
+    let extension = account.get_extension::<TransferHookAccount>()?;
+    if !bool::from(extension.transferring) {
+        return Err(ErrorCode::NotTransferring.into());
+    }
+    Ok(())
+}
+
+#[error_code]
+pub enum ErrorCode {
+    #[msg("Transfer hook invoked outside of a transfer")]
+    NotTransferring,
+    #[msg("Holder is not whitelisted")]
+    UnauthorizedPermission,
+}
@@ -6,3 +6,9 @@ pub use only_owner::*;
 
 pub mod only_permission_admin;
 pub use only_permission_admin::*;
+
+pub mod only_allowlisted;
+pub use only_allowlisted::*;
+
+pub mod not_blocked;
+pub use not_blocked::*;
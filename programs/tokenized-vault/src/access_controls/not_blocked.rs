@@ -0,0 +1,10 @@
+use anchor_lang::prelude::*;
+
+use crate::error::ErrorCode;
+
+pub fn not_blocked(blocked: &AccountInfo) -> Result<()> {
+    if blocked.owner == &crate::ID && !blocked.data_is_empty() {
+        return Err(ErrorCode::AddressBlocked.into());
+    }
+    Ok(())
+}
@@ -0,0 +1,15 @@
+use anchor_lang::prelude::*;
+
+use crate::access_controls::only_permission;
+use crate::states::{Access, Config};
+use crate::utils::WHITELISTED_PERMISSION;
+
+pub fn only_allowlisted(
+    config: &AccountLoader<Config>,
+    access: &Option<AccountLoader<Access>>,
+) -> Result<()> {
+    if !config.load()?.is_allowlist_enabled() {
+        return Ok(());
+    }
+    only_permission(access, WHITELISTED_PERMISSION)
+}
@@ -0,0 +1,282 @@
+//! Off-chain helpers to build vault instructions, enabled with the `client` feature.
+
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::instruction::Instruction;
+use anchor_lang::{system_program, InstructionData};
+use anchor_spl::associated_token::get_associated_token_address_with_program_id;
+use anchor_spl::token_2022::spl_token_2022;
+use anchor_spl::token_2022::spl_token_2022::extension::confidential_transfer::{
+    instruction::{
+        apply_pending_balance, withdraw, BatchedRangeProofU64Data,
+        CiphertextCommitmentEqualityProofData,
+    },
+    DecryptableBalance,
+};
+use spl_token_confidential_transfer_proof_extraction::instruction::ProofLocation;
+
+use crate::utils::{BLOCKED_SEED, CONFIG_SEED, MAX_DECIMALS, SHARES_MINT_SEED, USER_POSITION_SEED};
+
+fn find_address(seeds: &[&[u8]]) -> Pubkey {
+    Pubkey::find_program_address(seeds, &crate::ID).0
+}
+
+/// Builds a `redeem` of `shares` from the public balance of the user's shares ATA,
+/// paying the assets to `user_asset_vault`, a token account owned by `receiver_owner`.
+pub fn redeem(
+    user: &Pubkey,
+    asset_mint: &Pubkey,
+    asset_token_program: &Pubkey,
+    user_asset_vault: &Pubkey,
+    receiver_owner: &Pubkey,
+    shares: u64,
+) -> Instruction {
+    let config = find_address(&[CONFIG_SEED.as_bytes()]);
+    let shares_mint = find_address(&[SHARES_MINT_SEED.as_bytes()]);
+    let accounts = crate::accounts::Withdraw {
+        user: *user,
+        user_blocked: find_address(&[BLOCKED_SEED.as_bytes(), user.as_ref()]),
+        asset_mint: *asset_mint,
+        user_asset_vault: *user_asset_vault,
+        receiver_blocked: find_address(&[BLOCKED_SEED.as_bytes(), receiver_owner.as_ref()]),
+        asset_vault: get_associated_token_address_with_program_id(
+            &shares_mint,
+            asset_mint,
+            asset_token_program,
+        ),
+        shares_mint,
+        user_shares_vault: get_associated_token_address_with_program_id(
+            user,
+            &shares_mint,
+            &spl_token_2022::ID,
+        ),
+        config,
+        user_position: find_address(&[
+            USER_POSITION_SEED.as_bytes(),
+            config.as_ref(),
+            user.as_ref(),
+        ]),
+        asset_token_program: *asset_token_program,
+        token2022_program: spl_token_2022::ID,
+        system_program: system_program::ID,
+        rent: anchor_lang::solana_program::sysvar::rent::ID,
+    };
+    Instruction {
+        program_id: crate::ID,
+        accounts: accounts.to_account_metas(None),
+        data: crate::instruction::Redeem { shares }.data(),
+    }
+}
+
+/// Pending confidential credits to fold into the available balance before withdrawing.
+pub struct PendingBalance {
+    /// `pending_balance_credit_counter` of the shares account when the balance was decrypted
+    pub expected_credit_counter: u64,
+    /// Available balance after the pending credits are applied, encrypted with the owner's AE key
+    pub new_decryptable_available_balance: DecryptableBalance,
+}
+
+/// Builds the instructions redeeming `shares` held in the confidential balance of the
+/// user's shares ATA. They must be sent in a single transaction, in order:
+///
+/// 1. `ApplyPendingBalance`, when `pending` is set, so that confidential credits
+///    received since the last apply can be withdrawn.
+/// 2. Confidential-transfer `Withdraw` of `shares` to the public balance, followed by
+///    its ciphertext-commitment equality and range proof verifications (or reading
+///    them from context state accounts).
+/// 3. The vault `redeem`, which burns the shares from the public balance.
+///
+/// The proofs and decryptable balances are generated off-chain with the owner's
+/// ElGamal and AE keys, e.g. with `spl-token-confidential-transfer-proof-generation`.
+/// Should any step fail, the whole transaction reverts and the shares stay confidential.
+#[allow(clippy::too_many_arguments)]
+pub fn redeem_from_confidential_balance(
+    user: &Pubkey,
+    asset_mint: &Pubkey,
+    asset_token_program: &Pubkey,
+    user_asset_vault: &Pubkey,
+    receiver_owner: &Pubkey,
+    shares: u64,
+    pending: Option<PendingBalance>,
+    new_decryptable_available_balance: &DecryptableBalance,
+    equality_proof: ProofLocation<CiphertextCommitmentEqualityProofData>,
+    range_proof: ProofLocation<BatchedRangeProofU64Data>,
+) -> std::result::Result<Vec<Instruction>, ProgramError> {
+    let shares_mint = find_address(&[SHARES_MINT_SEED.as_bytes()]);
+    let user_shares_vault =
+        get_associated_token_address_with_program_id(user, &shares_mint, &spl_token_2022::ID);
+
+    let mut instructions = vec![];
+    if let Some(pending) = pending {
+        instructions.push(apply_pending_balance(
+            &spl_token_2022::ID,
+            &user_shares_vault,
+            pending.expected_credit_counter,
+            &pending.new_decryptable_available_balance,
+            user,
+            &[],
+        )?);
+    }
+    instructions.extend(withdraw(
+        &spl_token_2022::ID,
+        &user_shares_vault,
+        &shares_mint,
+        shares,
+        MAX_DECIMALS,
+        new_decryptable_available_balance,
+        user,
+        &[],
+        equality_proof,
+        range_proof,
+    )?);
+    instructions.push(redeem(
+        user,
+        asset_mint,
+        asset_token_program,
+        user_asset_vault,
+        receiver_owner,
+        shares,
+    ));
+    Ok(instructions)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anchor_spl::token_2022::spl_token_2022::extension::confidential_transfer::instruction::ConfidentialTransferInstruction;
+    use anchor_spl::token_2022::spl_token_2022::instruction::{
+        decode_instruction_type, TokenInstruction,
+    };
+
+    #[test]
+    fn redeem_checks_the_receiver_denylist() {
+        let (user, receiver) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let asset_mint = Pubkey::new_unique();
+        let receiver_asset_vault = get_associated_token_address_with_program_id(
+            &receiver,
+            &asset_mint,
+            &anchor_spl::token::ID,
+        );
+        let ix = redeem(
+            &user,
+            &asset_mint,
+            &anchor_spl::token::ID,
+            &receiver_asset_vault,
+            &receiver,
+            100,
+        );
+
+        assert_eq!(
+            ix.accounts[1].pubkey,
+            find_address(&[BLOCKED_SEED.as_bytes(), user.as_ref()])
+        );
+        assert_eq!(ix.accounts[3].pubkey, receiver_asset_vault);
+        assert_eq!(
+            ix.accounts[4].pubkey,
+            find_address(&[BLOCKED_SEED.as_bytes(), receiver.as_ref()])
+        );
+    }
+
+    #[test]
+    fn redeem_from_confidential_balance_applies_withdraws_then_redeems() {
+        let (user, receiver) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let asset_mint = Pubkey::new_unique();
+        let receiver_asset_vault = Pubkey::new_unique();
+        let (equality_proof, range_proof) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let instructions = redeem_from_confidential_balance(
+            &user,
+            &asset_mint,
+            &anchor_spl::token::ID,
+            &receiver_asset_vault,
+            &receiver,
+            100,
+            Some(PendingBalance {
+                expected_credit_counter: 2,
+                new_decryptable_available_balance: DecryptableBalance::default(),
+            }),
+            &DecryptableBalance::default(),
+            ProofLocation::ContextStateAccount(&equality_proof),
+            ProofLocation::ContextStateAccount(&range_proof),
+        )
+        .unwrap();
+        assert_eq!(instructions.len(), 3);
+
+        let shares_mint = find_address(&[SHARES_MINT_SEED.as_bytes()]);
+        let user_shares_vault =
+            get_associated_token_address_with_program_id(&user, &shares_mint, &spl_token_2022::ID);
+        let confidential_instruction = |ix: &Instruction| {
+            assert_eq!(ix.program_id, spl_token_2022::ID);
+            assert!(matches!(
+                TokenInstruction::unpack(&ix.data),
+                Ok(TokenInstruction::ConfidentialTransferExtension)
+            ));
+            decode_instruction_type::<ConfidentialTransferInstruction>(&ix.data[1..]).unwrap()
+        };
+
+        let apply = &instructions[0];
+        assert!(matches!(
+            confidential_instruction(apply),
+            ConfidentialTransferInstruction::ApplyPendingBalance
+        ));
+        assert_eq!(apply.accounts[0].pubkey, user_shares_vault);
+
+        let withdraw = &instructions[1];
+        assert!(matches!(
+            confidential_instruction(withdraw),
+            ConfidentialTransferInstruction::Withdraw
+        ));
+        assert_eq!(withdraw.accounts[0].pubkey, user_shares_vault);
+        assert_eq!(withdraw.accounts[1].pubkey, shares_mint);
+        assert_eq!(withdraw.accounts[2].pubkey, equality_proof);
+        assert_eq!(withdraw.accounts[3].pubkey, range_proof);
+
+        let redeem = &instructions[2];
+        assert_eq!(redeem.program_id, crate::ID);
+        assert_eq!(
+            redeem.data,
+            crate::instruction::Redeem { shares: 100 }.data()
+        );
+        assert_eq!(redeem.accounts[0].pubkey, user);
+        assert!(redeem.accounts[0].is_signer);
+        assert_eq!(redeem.accounts[3].pubkey, receiver_asset_vault);
+        assert_eq!(
+            redeem.accounts[6].pubkey,
+            find_address(&[SHARES_MINT_SEED.as_bytes()])
+        );
+        assert_eq!(redeem.accounts[7].pubkey, user_shares_vault);
+    }
+
+    #[test]
+    fn redeem_from_confidential_balance_skips_apply_without_pending_credits() {
+        let (user, receiver) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let asset_mint = Pubkey::new_unique();
+        let receiver_asset_vault = Pubkey::new_unique();
+        let (equality_proof, range_proof) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let instructions = redeem_from_confidential_balance(
+            &user,
+            &asset_mint,
+            &anchor_spl::token::ID,
+            &receiver_asset_vault,
+            &receiver,
+            100,
+            None,
+            &DecryptableBalance::default(),
+            ProofLocation::ContextStateAccount(&equality_proof),
+            ProofLocation::ContextStateAccount(&range_proof),
+        )
+        .unwrap();
+
+        // Without pending credits there is nothing to apply
+        assert_eq!(instructions.len(), 2);
+        assert_eq!(
+            instructions.last(),
+            Some(&redeem(
+                &user,
+                &asset_mint,
+                &anchor_spl::token::ID,
+                &receiver_asset_vault,
+                &receiver,
+                100,
+            ))
+        );
+    }
+}
@@ -10,4 +10,82 @@ pub enum ErrorCode {
     UnauthorizedAdminPermission,
     #[msg("Invalid asset mint decimals")]
     InvalidAssetMintDecimals,
+    #[msg("Deposit cap exceeded")]
+    DepositCapExceeded,
+    #[msg("User deposit limit exceeded")]
+    UserDepositLimitExceeded,
+    #[msg("Invalid remaining accounts")]
+    InvalidRemainingAccounts,
+    #[msg("Address is blocked")]
+    AddressBlocked,
+    #[msg("Withdrawal cooldown is active")]
+    CooldownActive,
+    #[msg("No pending redeem request")]
+    NoPendingRedeemRequest,
+    #[msg("No claimable redeem request")]
+    NoClaimableRedeemRequest,
+    #[msg("Synchronous deposits are disabled")]
+    SyncDepositsDisabled,
+    #[msg("Asynchronous deposits are disabled")]
+    AsyncDepositsDisabled,
+    #[msg("No pending deposits")]
+    NoPendingDeposits,
+    #[msg("Deposit request has not been settled")]
+    DepositNotSettled,
+    #[msg("Previous deposit request must be claimed first")]
+    UnclaimedDepositRequest,
+    #[msg("Previous redeem request must be claimed first")]
+    UnclaimedRedeemRequest,
+    #[msg("Invalid withdrawal epoch")]
+    InvalidWithdrawalEpoch,
+    #[msg("No pending deposit request")]
+    NoPendingDepositRequest,
+    #[msg("Cancellations are locked during settlement")]
+    CancellationsLocked,
+    #[msg("Withdrawal rate limit exceeded")]
+    WithdrawalRateLimited,
+    #[msg("Invalid withdrawal rate limit")]
+    InvalidWithdrawRateLimit,
+    #[msg("Cannot withdraw in the same slot as a deposit")]
+    SameSlotWithdrawal,
+    #[msg("Cannot deposit in the same slot as a report")]
+    DepositInReportSlot,
+    #[msg("Math overflow")]
+    MathOverflow,
+    #[msg("Asset mint has a freeze authority")]
+    AssetMintFreezeAuthority,
+    #[msg("Asset mint has a permanent delegate")]
+    AssetMintPermanentDelegate,
+    #[msg("Asset mint has a transfer hook")]
+    AssetMintTransferHook,
+    #[msg("Asset mint allows confidential transfers")]
+    AssetMintConfidentialTransfers,
+    #[msg("Asset mint is non-transferable")]
+    AssetMintNonTransferable,
+    #[msg("Shares are non-transferable")]
+    NonTransferableShares,
+    #[msg("Asset mint is not the native mint")]
+    NotNativeMint,
+    #[msg("Signer is neither the owner nor the delegate of the token account")]
+    InvalidTokenAccountAuthority,
+    #[msg("Not enough idle assets in the vault")]
+    InsufficientIdleAssets,
+    #[msg("Strategy still has debt")]
+    StrategyHasDebt,
+    #[msg("Strategy returned less than the requested assets")]
+    StrategyWithdrawShortfall,
+    #[msg("Debt is already at the target")]
+    DebtUnchanged,
+    #[msg("Invalid performance fee")]
+    InvalidPerformanceFee,
+    #[msg("Fee recipient shares account is required")]
+    MissingFeeRecipient,
+    #[msg("Invalid profit unlock time")]
+    InvalidProfitUnlockTime,
+    #[msg("Metadata field is reserved for the vault parameters")]
+    ReservedMetadataField,
+    #[msg("Withdrawal cooldown requires non-transferable shares")]
+    CooldownRequiresNonTransferableShares,
+    #[msg("Cannot withdraw in the same slot as a report")]
+    WithdrawalInReportSlot,
 }
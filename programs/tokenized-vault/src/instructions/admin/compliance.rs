@@ -0,0 +1,51 @@
+use anchor_lang::prelude::*;
+
+use crate::states::{Access, Blocked};
+use crate::utils::{ACCESS_SEED, BLOCKED_SEED};
+
+#[derive(Accounts)]
+pub struct BlockAddress<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    #[account(seeds = [ACCESS_SEED.as_bytes(), authority.key().as_ref()], bump)]
+    pub access: Option<AccountLoader<'info, Access>>,
+    /// CHECK: This is the address to block
+    pub target: UncheckedAccount<'info>,
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + Blocked::INIT_SPACE,
+        seeds = [BLOCKED_SEED.as_bytes(), target.key().as_ref()],
+        bump
+    )]
+    pub blocked: AccountLoader<'info, Blocked>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct UnblockAddress<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    #[account(seeds = [ACCESS_SEED.as_bytes(), authority.key().as_ref()], bump)]
+    pub access: Option<AccountLoader<'info, Access>>,
+    /// CHECK: This is the address to unblock
+    pub target: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        close = authority,
+        seeds = [BLOCKED_SEED.as_bytes(), target.key().as_ref()],
+        bump
+    )]
+    pub blocked: AccountLoader<'info, Blocked>,
+}
+
+pub fn process_block_address(ctx: Context<BlockAddress>) -> Result<()> {
+    let blocked = &mut ctx.accounts.blocked.load_init()?;
+    blocked.initialize(ctx.accounts.target.key(), Clock::get()?.unix_timestamp)?;
+    Ok(())
+}
+
+pub fn process_unblock_address(ctx: Context<UnblockAddress>) -> Result<()> {
+    ctx.accounts.blocked.load()?.release()?;
+    Ok(())
+}
@@ -1,29 +1,59 @@
 use anchor_lang::prelude::*;
 
-use crate::states::{Config, CreationFeeUpdated, FeeRecipientUpdated};
+use crate::states::Config;
 use crate::utils::CONFIG_SEED;
 
 #[derive(Accounts)]
 pub struct UpdateConfig<'info> {
     #[account(mut)]
     pub owner: Signer<'info>,
-    #[account(seeds = [CONFIG_SEED.as_bytes()], bump)]
+    #[account(mut, seeds = [CONFIG_SEED.as_bytes()], bump)]
     pub config: AccountLoader<'info, Config>,
 }
 
-pub fn process_update_fee_recipient(
-    ctx: Context<UpdateConfig>,
-    fee_recipient: Pubkey,
-) -> Result<()> {
+pub fn process_set_allowlist_enabled(ctx: Context<UpdateConfig>, enabled: bool) -> Result<()> {
     let config = &mut ctx.accounts.config.load_mut()?;
-    config.fee_recipient = fee_recipient;
-    emit!(FeeRecipientUpdated { fee_recipient });
+    config.set_allowlist_enabled(enabled)?;
     Ok(())
 }
 
-pub fn process_update_creation_fee(ctx: Context<UpdateConfig>, creation_fee: u64) -> Result<()> {
+pub fn process_set_async_deposits(ctx: Context<UpdateConfig>, enabled: bool) -> Result<()> {
     let config = &mut ctx.accounts.config.load_mut()?;
-    config.creation_fee = creation_fee;
-    emit!(CreationFeeUpdated { creation_fee });
+    if enabled {
+        config.check_transferable_shares()?;
+    }
+    config.set_async_deposits(enabled)?;
     Ok(())
 }
+
+pub fn process_set_report_slot_guard(ctx: Context<UpdateConfig>, enabled: bool) -> Result<()> {
+    let config = &mut ctx.accounts.config.load_mut()?;
+    config.set_report_slot_guard(enabled)?;
+    Ok(())
+}
+
+pub fn process_set_profit_max_unlock_time(
+    ctx: Context<UpdateConfig>,
+    profit_max_unlock_time: u64,
+) -> Result<()> {
+    let config = &mut ctx.accounts.config.load_mut()?;
+    config.set_profit_max_unlock_time(profit_max_unlock_time)?;
+    Ok(())
+}
+
+// pub fn process_update_fee_recipient(
+//     ctx: Context<UpdateConfig>,
+//     fee_recipient: Pubkey,
+// ) -> Result<()> {
+//     let config = &mut ctx.accounts.config.load_mut()?;
+//     config.fee_recipient = fee_recipient;
+//     emit!(FeeRecipientUpdated { fee_recipient });
+//     Ok(())
+// }
+//
+// pub fn process_update_creation_fee(ctx: Context<UpdateConfig>, creation_fee: u64) -> Result<()> {
+//     let config = &mut ctx.accounts.config.load_mut()?;
+//     config.creation_fee = creation_fee;
+//     emit!(CreationFeeUpdated { creation_fee });
+//     Ok(())
+// }
@@ -0,0 +1,44 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, Token2022};
+
+use crate::states::Config;
+use crate::utils::{
+    account::update_account_lamports_to_minimum_balance, publish_vault_metadata, CONFIG_SEED,
+    SHARES_MINT_SEED,
+};
+
+#[derive(Accounts)]
+pub struct UpdateFees<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    #[account(mut, seeds = [CONFIG_SEED.as_bytes()], bump)]
+    pub config: AccountLoader<'info, Config>,
+    #[account(mut, seeds = [SHARES_MINT_SEED.as_bytes()], bump, mint::token_program = token2022_program)]
+    pub shares_mint: InterfaceAccount<'info, Mint>,
+
+    pub token2022_program: Program<'info, Token2022>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+/// Sets the fee charged on reported strategy gains and re-publishes it in the shares metadata.
+pub fn process_set_performance_fee(
+    ctx: Context<UpdateFees>,
+    fee_bps: u64,
+    fee_recipient: Pubkey,
+) -> Result<()> {
+    let config = &mut ctx.accounts.config.load_mut()?;
+    config.set_performance_fee(fee_bps, fee_recipient)?;
+    publish_vault_metadata(
+        ctx.accounts.token2022_program.to_account_info(),
+        ctx.accounts.shares_mint.to_account_info(),
+        ctx.bumps.shares_mint,
+        config,
+    )?;
+    update_account_lamports_to_minimum_balance(
+        ctx.accounts.shares_mint.to_account_info(),
+        ctx.accounts.owner.to_account_info(),
+        ctx.accounts.rent.to_account_info(),
+        ctx.accounts.system_program.to_account_info(),
+    )
+}
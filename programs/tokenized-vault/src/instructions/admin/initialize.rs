@@ -1,14 +1,25 @@
 use anchor_lang::prelude::*;
 
+use anchor_lang::solana_program::program::invoke;
+use anchor_lang::system_program::{create_account, CreateAccount};
+
 use crate::states::{Access, Config, ConfigInitialized};
 use crate::utils::{
-    account::update_account_lamports_to_minimum_balance, ACCESS_SEED, CONFIG_SEED, MAX_DECIMALS,
-    SHARES_MINT_SEED,
+    account::update_account_lamports_to_minimum_balance, check_asset_mint, publish_vault_metadata,
+    ACCESS_SEED, CONFIG_SEED, MAX_DECIMALS, SHARES_CONFIDENTIAL_TRANSFERS, SHARES_MINT_SEED,
+    SHARES_NON_TRANSFERABLE, SHARES_SCALED_UI_AMOUNT, SHARES_TRANSFER_HOOK,
+    SHARES_TRANSFER_HOOK_PROGRAM_ID,
 };
 use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token_2022::spl_token_2022::{
+    self,
+    extension::{confidential_transfer, scaled_ui_amount, ExtensionType},
+};
 use anchor_spl::token_interface::{
-    token_metadata_initialize, Mint, Token2022, TokenAccount, TokenInterface,
-    TokenMetadataInitialize,
+    initialize_mint2, metadata_pointer_initialize, non_transferable_mint_initialize,
+    token_metadata_initialize, transfer_hook_initialize, InitializeMint2,
+    MetadataPointerInitialize, Mint, NonTransferableMintInitialize, Token2022, TokenAccount,
+    TokenInterface, TokenMetadataInitialize, TransferHookInitialize,
 };
 
 use crate::error::ErrorCode;
@@ -32,18 +43,9 @@ pub struct Initialize<'info> {
     )]
     pub asset_vault: InterfaceAccount<'info, TokenAccount>,
 
-    #[account(
-        init,
-        seeds = [SHARES_MINT_SEED.as_bytes()],
-        bump,
-        payer = owner,
-        mint::decimals = MAX_DECIMALS,
-        mint::authority = shares_mint.key(),
-        mint::token_program = token2022_program,
-        extensions::metadata_pointer::authority = shares_mint.key(),
-        extensions::metadata_pointer::metadata_address = shares_mint,
-    )]
-    pub shares_mint: InterfaceAccount<'info, Mint>,
+    /// CHECK: Created with the extensions selected by `options` in `process_initialize`
+    #[account(mut, seeds = [SHARES_MINT_SEED.as_bytes()], bump)]
+    pub shares_mint: UncheckedAccount<'info>,
 
     pub asset_token_program: Interface<'info, TokenInterface>,
     pub token2022_program: Program<'info, Token2022>,
@@ -52,10 +54,15 @@ pub struct Initialize<'info> {
     pub rent: Sysvar<'info, Rent>,
 }
 
-pub fn process_initialize(ctx: Context<Initialize>, metadata: TokenMetadataArgs) -> Result<()> {
+pub fn process_initialize(
+    ctx: Context<Initialize>,
+    metadata: TokenMetadataArgs,
+    options: u16,
+) -> Result<()> {
     if ctx.accounts.asset_mint.decimals > MAX_DECIMALS {
         return Err(ErrorCode::InvalidAssetMintDecimals.into());
     }
+    check_asset_mint(&ctx.accounts.asset_mint, options)?;
 
     let config = &mut ctx.accounts.config.load_init()?;
     let access = &mut ctx.accounts.access.load_init()?;
@@ -67,9 +74,20 @@ pub fn process_initialize(ctx: Context<Initialize>, metadata: TokenMetadataArgs)
     config.owner = ctx.accounts.owner.key();
     config.asset_mint = ctx.accounts.asset_mint.key();
     config.decimals_offset = MAX_DECIMALS - ctx.accounts.asset_mint.decimals;
+    config.init_limits();
+    config.non_transferable_shares = (options & SHARES_NON_TRANSFERABLE != 0) as u8;
+    config.shares_transfer_hook = (options & SHARES_TRANSFER_HOOK != 0) as u8;
 
+    ctx.accounts
+        .create_shares_mint(ctx.bumps.shares_mint, options)?;
     ctx.accounts
         .initialize_shares_metadata(ctx.bumps.shares_mint, metadata)?;
+    publish_vault_metadata(
+        ctx.accounts.token2022_program.to_account_info(),
+        ctx.accounts.shares_mint.to_account_info(),
+        ctx.bumps.shares_mint,
+        config,
+    )?;
 
     update_account_lamports_to_minimum_balance(
         ctx.accounts.shares_mint.to_account_info(),
@@ -93,6 +111,112 @@ pub struct TokenMetadataArgs {
 }
 
 impl<'info> Initialize<'info> {
+    pub fn create_shares_mint(&self, bump: u8, options: u16) -> Result<()> {
+        let seeds = &[SHARES_MINT_SEED.as_bytes(), &[bump]];
+        let signer = &[&seeds[..]];
+        let transfer_hook = options & SHARES_TRANSFER_HOOK != 0;
+        let non_transferable = options & SHARES_NON_TRANSFERABLE != 0;
+        let scaled_ui_amount = options & SHARES_SCALED_UI_AMOUNT != 0;
+        let confidential_transfers = options & SHARES_CONFIDENTIAL_TRANSFERS != 0;
+
+        let mut extensions = vec![ExtensionType::MetadataPointer];
+        if transfer_hook {
+            extensions.push(ExtensionType::TransferHook);
+        }
+        if non_transferable {
+            extensions.push(ExtensionType::NonTransferable);
+        }
+        if scaled_ui_amount {
+            extensions.push(ExtensionType::ScaledUiAmount);
+        }
+        if confidential_transfers {
+            extensions.push(ExtensionType::ConfidentialTransferMint);
+        }
+        let space =
+            ExtensionType::try_calculate_account_len::<spl_token_2022::state::Mint>(&extensions)?;
+        create_account(
+            CpiContext::new_with_signer(
+                self.system_program.to_account_info(),
+                CreateAccount {
+                    from: self.owner.to_account_info(),
+                    to: self.shares_mint.to_account_info(),
+                },
+                signer,
+            ),
+            Rent::get()?.minimum_balance(space),
+            space as u64,
+            &self.token2022_program.key(),
+        )?;
+
+        metadata_pointer_initialize(
+            CpiContext::new(
+                self.token2022_program.to_account_info(),
+                MetadataPointerInitialize {
+                    token_program_id: self.token2022_program.to_account_info(),
+                    mint: self.shares_mint.to_account_info(),
+                },
+            ),
+            Some(self.shares_mint.key()),
+            Some(self.shares_mint.key()),
+        )?;
+        if transfer_hook {
+            transfer_hook_initialize(
+                CpiContext::new(
+                    self.token2022_program.to_account_info(),
+                    TransferHookInitialize {
+                        token_program_id: self.token2022_program.to_account_info(),
+                        mint: self.shares_mint.to_account_info(),
+                    },
+                ),
+                Some(self.shares_mint.key()),
+                Some(SHARES_TRANSFER_HOOK_PROGRAM_ID),
+            )?;
+        }
+        if non_transferable {
+            non_transferable_mint_initialize(CpiContext::new(
+                self.token2022_program.to_account_info(),
+                NonTransferableMintInitialize {
+                    token_program_id: self.token2022_program.to_account_info(),
+                    mint: self.shares_mint.to_account_info(),
+                },
+            ))?;
+        }
+        if scaled_ui_amount {
+            // One share displays as one asset until the first keeper refresh
+            let ix = scaled_ui_amount::instruction::initialize(
+                self.token2022_program.key,
+                self.shares_mint.key,
+                Some(self.shares_mint.key()),
+                1.0,
+            )?;
+            invoke(&ix, &[self.shares_mint.to_account_info()])?;
+        }
+        if confidential_transfers {
+            // Accounts are approved on configuration and no auditor is set, so
+            // confidential balances are visible to their owners only
+            let ix = confidential_transfer::instruction::initialize_mint(
+                self.token2022_program.key,
+                self.shares_mint.key,
+                Some(self.shares_mint.key()),
+                true,
+                None,
+            )?;
+            invoke(&ix, &[self.shares_mint.to_account_info()])?;
+        }
+        initialize_mint2(
+            CpiContext::new(
+                self.token2022_program.to_account_info(),
+                InitializeMint2 {
+                    mint: self.shares_mint.to_account_info(),
+                },
+            ),
+            MAX_DECIMALS,
+            &self.shares_mint.key(),
+            None,
+        )?;
+        Ok(())
+    }
+
     pub fn initialize_shares_metadata(&self, bump: u8, args: TokenMetadataArgs) -> Result<()> {
         let seeds = &[SHARES_MINT_SEED.as_bytes(), &[bump]];
         let signer = &[&seeds[..]];
@@ -0,0 +1,88 @@
+use anchor_lang::prelude::*;
+
+use crate::states::{Access, Config, UserPosition};
+use crate::utils::{ACCESS_SEED, CONFIG_SEED, USER_POSITION_SEED};
+
+#[derive(Accounts)]
+pub struct UpdateLimits<'info> {
+    pub authority: Signer<'info>,
+    #[account(seeds = [ACCESS_SEED.as_bytes(), authority.key().as_ref()], bump)]
+    pub access: Option<AccountLoader<'info, Access>>,
+    #[account(mut, seeds = [CONFIG_SEED.as_bytes()], bump)]
+    pub config: AccountLoader<'info, Config>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateUserLimits<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    #[account(seeds = [ACCESS_SEED.as_bytes(), authority.key().as_ref()], bump)]
+    pub access: Option<AccountLoader<'info, Access>>,
+    #[account(seeds = [CONFIG_SEED.as_bytes()], bump)]
+    pub config: AccountLoader<'info, Config>,
+    /// CHECK: This is the account whose deposit limit is overridden
+    pub user: UncheckedAccount<'info>,
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = 8 + UserPosition::INIT_SPACE,
+        seeds = [USER_POSITION_SEED.as_bytes(), config.key().as_ref(), user.key().as_ref()],
+        bump
+    )]
+    pub user_position: AccountLoader<'info, UserPosition>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+pub fn process_set_deposit_cap(ctx: Context<UpdateLimits>, deposit_cap: u64) -> Result<()> {
+    let config = &mut ctx.accounts.config.load_mut()?;
+    config.set_deposit_cap(deposit_cap)?;
+    Ok(())
+}
+
+pub fn process_set_default_deposit_limit(
+    ctx: Context<UpdateLimits>,
+    user_deposit_limit: u64,
+) -> Result<()> {
+    let config = &mut ctx.accounts.config.load_mut()?;
+    config.set_user_deposit_limit(user_deposit_limit)?;
+    Ok(())
+}
+
+pub fn process_set_withdraw_cooldown(
+    ctx: Context<UpdateLimits>,
+    withdraw_cooldown: u64,
+) -> Result<()> {
+    let config = &mut ctx.accounts.config.load_mut()?;
+    config.set_withdraw_cooldown(withdraw_cooldown)?;
+    Ok(())
+}
+
+pub fn process_set_withdraw_rate_limit(
+    ctx: Context<UpdateLimits>,
+    bps: u64,
+    window: u64,
+) -> Result<()> {
+    let config = &mut ctx.accounts.config.load_mut()?;
+    config.set_withdraw_rate_limit(bps, window)?;
+    Ok(())
+}
+
+pub fn process_reset_withdraw_rate_limit(ctx: Context<UpdateLimits>) -> Result<()> {
+    let config = &mut ctx.accounts.config.load_mut()?;
+    config.reset_withdraw_rate_limit()?;
+    Ok(())
+}
+
+pub fn process_set_user_deposit_limit(
+    ctx: Context<UpdateUserLimits>,
+    deposit_limit: Option<u64>,
+) -> Result<()> {
+    let mut user_position = UserPosition::load_or_init(
+        &ctx.accounts.user_position,
+        ctx.accounts.config.key(),
+        ctx.accounts.user.key(),
+    )?;
+    user_position.set_deposit_limit(deposit_limit)?;
+    Ok(())
+}
@@ -0,0 +1,113 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{spl_token_metadata_interface::state::Field, Mint, Token2022};
+
+use crate::instructions::TokenMetadataArgs;
+use crate::states::{Access, Config, MetadataFieldUpdated, SharesMetadataUpdated};
+use crate::utils::{
+    account::update_account_lamports_to_minimum_balance, publish_vault_metadata,
+    update_shares_metadata_field, ACCESS_SEED, CONFIG_SEED, SHARES_MINT_SEED,
+};
+
+#[derive(Accounts)]
+pub struct UpdateMetadata<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    #[account(seeds = [ACCESS_SEED.as_bytes(), authority.key().as_ref()], bump)]
+    pub access: Option<AccountLoader<'info, Access>>,
+    #[account(mut, seeds = [SHARES_MINT_SEED.as_bytes()], bump, mint::token_program = token2022_program)]
+    pub shares_mint: InterfaceAccount<'info, Mint>,
+
+    pub token2022_program: Program<'info, Token2022>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+#[derive(Accounts)]
+pub struct SyncMetadata<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    #[account(seeds = [ACCESS_SEED.as_bytes(), authority.key().as_ref()], bump)]
+    pub access: Option<AccountLoader<'info, Access>>,
+    #[account(seeds = [CONFIG_SEED.as_bytes()], bump)]
+    pub config: AccountLoader<'info, Config>,
+    #[account(mut, seeds = [SHARES_MINT_SEED.as_bytes()], bump, mint::token_program = token2022_program)]
+    pub shares_mint: InterfaceAccount<'info, Mint>,
+
+    pub token2022_program: Program<'info, Token2022>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+pub fn process_update_shares_metadata(
+    ctx: Context<UpdateMetadata>,
+    metadata: TokenMetadataArgs,
+) -> Result<()> {
+    let bump = ctx.bumps.shares_mint;
+    ctx.accounts
+        .update_field(bump, Field::Name, metadata.name.clone())?;
+    ctx.accounts
+        .update_field(bump, Field::Symbol, metadata.symbol.clone())?;
+    ctx.accounts
+        .update_field(bump, Field::Uri, metadata.uri.clone())?;
+    ctx.accounts.top_up_rent()?;
+    emit!(SharesMetadataUpdated {
+        name: metadata.name,
+        symbol: metadata.symbol,
+        uri: metadata.uri,
+    });
+    Ok(())
+}
+
+pub fn process_set_metadata_field(
+    ctx: Context<UpdateMetadata>,
+    key: String,
+    value: String,
+) -> Result<()> {
+    Config::check_unreserved_metadata_key(&key)?;
+    ctx.accounts.update_field(
+        ctx.bumps.shares_mint,
+        Field::Key(key.clone()),
+        value.clone(),
+    )?;
+    ctx.accounts.top_up_rent()?;
+    emit!(MetadataFieldUpdated { key, value });
+    Ok(())
+}
+
+/// Re-publishes the vault parameters, e.g. after a program upgrade.
+pub fn process_sync_vault_metadata(ctx: Context<SyncMetadata>) -> Result<()> {
+    publish_vault_metadata(
+        ctx.accounts.token2022_program.to_account_info(),
+        ctx.accounts.shares_mint.to_account_info(),
+        ctx.bumps.shares_mint,
+        &*ctx.accounts.config.load()?,
+    )?;
+    update_account_lamports_to_minimum_balance(
+        ctx.accounts.shares_mint.to_account_info(),
+        ctx.accounts.authority.to_account_info(),
+        ctx.accounts.rent.to_account_info(),
+        ctx.accounts.system_program.to_account_info(),
+    )
+}
+
+impl UpdateMetadata<'_> {
+    pub fn update_field(&self, bump: u8, field: Field, value: String) -> Result<()> {
+        update_shares_metadata_field(
+            self.token2022_program.to_account_info(),
+            self.shares_mint.to_account_info(),
+            bump,
+            field,
+            value,
+        )
+    }
+
+    /// Metadata is stored in the mint account, which grows with longer values.
+    pub fn top_up_rent(&self) -> Result<()> {
+        update_account_lamports_to_minimum_balance(
+            self.shares_mint.to_account_info(),
+            self.authority.to_account_info(),
+            self.rent.to_account_info(),
+            self.system_program.to_account_info(),
+        )
+    }
+}
@@ -7,5 +7,29 @@ pub use permission::*;
 pub mod permission_admin;
 pub use permission_admin::*;
 
-// pub mod config;
-// pub use config::*;
+pub mod limits;
+pub use limits::*;
+
+pub mod config;
+pub use config::*;
+
+pub mod compliance;
+pub use compliance::*;
+
+pub mod settlement;
+pub use settlement::*;
+
+pub mod share_multiplier;
+pub use share_multiplier::*;
+
+pub mod metadata;
+pub use metadata::*;
+
+pub mod strategy;
+pub use strategy::*;
+
+pub mod report;
+pub use report::*;
+
+pub mod fees;
+pub use fees::*;
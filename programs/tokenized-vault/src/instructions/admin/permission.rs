@@ -1,5 +1,8 @@
 use anchor_lang::prelude::*;
 
+use anchor_lang::system_program::{create_account, CreateAccount};
+
+use crate::error::ErrorCode;
 use crate::states::Access;
 use crate::utils::ACCESS_SEED;
 
@@ -29,6 +32,15 @@ pub struct UpdatePermission<'info> {
     pub rent: Sysvar<'info, Rent>,
 }
 
+#[derive(Accounts)]
+pub struct UpdatePermissionBatch<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    #[account(seeds = [ACCESS_SEED.as_bytes(), admin.key().as_ref()], bump)]
+    pub admin_access: AccountLoader<'info, Access>,
+    pub system_program: Program<'info, System>,
+}
+
 pub fn process_grant_permission(ctx: Context<UpdatePermission>, permission: u128) -> Result<()> {
     let mut to_access = match ctx.accounts.to_access.load_mut() {
         Ok(r) => r,
@@ -54,3 +66,65 @@ pub fn process_revoke_permission(ctx: Context<UpdatePermission>, permission: u12
     to_access.revoke_permission(permission)?;
     Ok(())
 }
+
+/// Remaining accounts are `[to, to_access]` pairs; missing `to_access` PDAs are created.
+pub fn process_grant_permission_batch<'info>(
+    ctx: Context<'_, '_, 'info, 'info, UpdatePermissionBatch<'info>>,
+    permission: u128,
+) -> Result<()> {
+    if ctx.remaining_accounts.is_empty() || ctx.remaining_accounts.len() % 2 != 0 {
+        return Err(ErrorCode::InvalidRemainingAccounts.into());
+    }
+    for pair in ctx.remaining_accounts.chunks(2) {
+        let (to, to_access) = (&pair[0], &pair[1]);
+        ctx.accounts.create_access_if_needed(to, to_access)?;
+        let loader = AccountLoader::<Access>::try_from_unchecked(&crate::ID, to_access)?;
+        {
+            let mut access = match loader.load_mut() {
+                Ok(r) => r,
+                Err(_err) => {
+                    let mut access = loader.load_init()?;
+                    access.initialize(to.key())?;
+                    access
+                }
+            };
+            access.grant_permission(permission)?;
+        }
+        loader.exit(&crate::ID)?;
+    }
+    Ok(())
+}
+
+impl<'info> UpdatePermissionBatch<'info> {
+    pub fn create_access_if_needed(
+        &self,
+        to: &AccountInfo<'info>,
+        to_access: &AccountInfo<'info>,
+    ) -> Result<()> {
+        let (expected_access, bump) =
+            Pubkey::find_program_address(&[ACCESS_SEED.as_bytes(), to.key.as_ref()], &crate::ID);
+        if expected_access != to_access.key() {
+            return Err(ErrorCode::InvalidRemainingAccounts.into());
+        }
+        if !to_access.data_is_empty() {
+            return Ok(());
+        }
+        let space = 8 + Access::INIT_SPACE;
+        let seeds = &[ACCESS_SEED.as_bytes(), to.key.as_ref(), &[bump]];
+        let signer_seeds = &[&seeds[..]];
+        create_account(
+            CpiContext::new_with_signer(
+                self.system_program.to_account_info(),
+                CreateAccount {
+                    from: self.admin.to_account_info(),
+                    to: to_access.clone(),
+                },
+                signer_seeds,
+            ),
+            Rent::get()?.minimum_balance(space),
+            space as u64,
+            &crate::ID,
+        )?;
+        Ok(())
+    }
+}
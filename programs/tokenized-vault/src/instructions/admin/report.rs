@@ -0,0 +1,203 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{
+    burn_checked, mint_to_checked, BurnChecked, Mint, MintToChecked, Token2022, TokenAccount,
+    TokenInterface,
+};
+
+use strategy_interface::onchain;
+
+use crate::error::ErrorCode;
+use crate::states::{Access, Config, Strategy, StrategyReported};
+use crate::utils::{
+    preview_deposit_internal, ACCESS_SEED, CONFIG_SEED, LOCKED_SHARES_SEED, SHARES_MINT_SEED,
+    STRATEGY_SEED,
+};
+
+#[derive(Accounts)]
+pub struct Report<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    #[account(seeds = [ACCESS_SEED.as_bytes(), authority.key().as_ref()], bump)]
+    pub access: Option<AccountLoader<'info, Access>>,
+    #[account(mut, seeds = [CONFIG_SEED.as_bytes()], bump, has_one = asset_mint)]
+    pub config: AccountLoader<'info, Config>,
+    #[account(
+        mut,
+        seeds = [STRATEGY_SEED.as_bytes(), config.key().as_ref(), strategy_program.key().as_ref()],
+        bump,
+        has_one = strategy_program
+    )]
+    pub strategy: AccountLoader<'info, Strategy>,
+    /// CHECK: Matched against the strategy account
+    #[account(executable)]
+    pub strategy_program: UncheckedAccount<'info>,
+    #[account(address = strategy.load()?.asset_account)]
+    pub strategy_asset_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(mint::token_program = asset_token_program)]
+    pub asset_mint: InterfaceAccount<'info, Mint>,
+    #[account(associated_token::mint = asset_mint, associated_token::authority = shares_mint, associated_token::token_program = asset_token_program)]
+    pub asset_vault: InterfaceAccount<'info, TokenAccount>,
+    #[account(mut, seeds = [SHARES_MINT_SEED.as_bytes()], bump, mint::token_program = token2022_program)]
+    pub shares_mint: InterfaceAccount<'info, Mint>,
+    /// Shares account of the fee recipient, required when fees are charged
+    #[account(
+        mut,
+        token::mint = shares_mint,
+        token::authority = config.load()?.fee_recipient,
+        token::token_program = token2022_program
+    )]
+    pub fee_recipient_shares_vault: Option<InterfaceAccount<'info, TokenAccount>>,
+    /// Holds the profit shares until they unlock
+    #[account(
+        init_if_needed,
+        payer = authority,
+        seeds = [LOCKED_SHARES_SEED.as_bytes()],
+        bump,
+        token::mint = shares_mint,
+        token::authority = shares_mint,
+        token::token_program = token2022_program
+    )]
+    pub locked_shares_vault: InterfaceAccount<'info, TokenAccount>,
+
+    pub asset_token_program: Interface<'info, TokenInterface>,
+    pub token2022_program: Program<'info, Token2022>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Marks the strategy's debt to the assets it reports. Gains are charged the performance
+/// fee, minted as shares to the fee recipient, and the rest is locked as profit shares that
+/// unlock linearly over `profit_max_unlock_time`. Losses are absorbed by the locked profit
+/// first, then lower the assets backing every share.
+pub fn process_report<'info>(ctx: Context<'_, '_, 'info, 'info, Report<'info>>) -> Result<()> {
+    let strategy_assets = onchain::total_assets(
+        &ctx.accounts.strategy_program.to_account_info(),
+        &[
+            (ctx.accounts.strategy.to_account_info(), false),
+            (ctx.accounts.strategy_asset_account.to_account_info(), false),
+        ],
+        ctx.remaining_accounts,
+    )?;
+    let clock = Clock::get()?;
+    let now = clock.unix_timestamp;
+    let (gain, loss) = ctx
+        .accounts
+        .strategy
+        .load_mut()?
+        .report(strategy_assets, now);
+
+    let mut config = ctx.accounts.config.load_mut()?;
+    let unlocked_shares = config.release_unlocked_shares(now);
+    let total_supply = ctx.accounts.shares_mint.supply - unlocked_shares;
+    config.total_debt = config.total_debt.saturating_add(gain).saturating_sub(loss);
+    config.last_report_slot = clock.slot;
+    let total_assets = config.total_assets(ctx.accounts.asset_vault.amount);
+
+    let fees = config.performance_fee(gain);
+    let locked_profit = if config.profit_max_unlock_time > 0 {
+        gain - fees
+    } else {
+        0
+    };
+    // Priced before the fees and the locked profit accrue, so that the fee shares are
+    // worth `fees` and the share price does not move with the locked profit
+    let assets_before = total_assets - fees - locked_profit;
+    let fee_shares =
+        preview_deposit_internal(fees, assets_before, total_supply, config.decimals_offset)?;
+    let profit_shares = preview_deposit_internal(
+        locked_profit,
+        assets_before,
+        total_supply,
+        config.decimals_offset,
+    )?;
+    let loss_shares = preview_deposit_internal(
+        loss,
+        total_assets + loss,
+        total_supply,
+        config.decimals_offset,
+    )?
+    .min(config.locked_shares);
+    config.locked_shares -= loss_shares;
+    config.lock_shares(profit_shares, now);
+
+    let bump = ctx.bumps.shares_mint;
+    if unlocked_shares + loss_shares > 0 {
+        ctx.accounts
+            .burn_locked_shares(bump, unlocked_shares + loss_shares)?;
+    }
+    if fee_shares > 0 {
+        ctx.accounts.mint_fee_shares(bump, fee_shares)?;
+    }
+    if profit_shares > 0 {
+        ctx.accounts.mint_locked_shares(bump, profit_shares)?;
+    }
+    emit!(StrategyReported {
+        strategy_program: ctx.accounts.strategy_program.key(),
+        gain,
+        loss,
+        fees,
+        total_assets,
+    });
+    Ok(())
+}
+
+impl Report<'_> {
+    pub fn mint_locked_shares(&self, bump: u8, amount: u64) -> Result<()> {
+        let seeds = &[SHARES_MINT_SEED.as_bytes(), &[bump]];
+        let signer_seeds = &[&seeds[..]];
+        mint_to_checked(
+            CpiContext::new_with_signer(
+                self.token2022_program.to_account_info(),
+                MintToChecked {
+                    mint: self.shares_mint.to_account_info(),
+                    authority: self.shares_mint.to_account_info(),
+                    to: self.locked_shares_vault.to_account_info(),
+                },
+                signer_seeds,
+            ),
+            amount,
+            self.shares_mint.decimals,
+        )?;
+        Ok(())
+    }
+    pub fn burn_locked_shares(&self, bump: u8, amount: u64) -> Result<()> {
+        let seeds = &[SHARES_MINT_SEED.as_bytes(), &[bump]];
+        let signer_seeds = &[&seeds[..]];
+        burn_checked(
+            CpiContext::new_with_signer(
+                self.token2022_program.to_account_info(),
+                BurnChecked {
+                    mint: self.shares_mint.to_account_info(),
+                    authority: self.shares_mint.to_account_info(),
+                    from: self.locked_shares_vault.to_account_info(),
+                },
+                signer_seeds,
+            ),
+            amount,
+            self.shares_mint.decimals,
+        )?;
+        Ok(())
+    }
+    pub fn mint_fee_shares(&self, bump: u8, amount: u64) -> Result<()> {
+        let fee_recipient_shares_vault = self
+            .fee_recipient_shares_vault
+            .as_ref()
+            .ok_or(ErrorCode::MissingFeeRecipient)?;
+        let seeds = &[SHARES_MINT_SEED.as_bytes(), &[bump]];
+        let signer_seeds = &[&seeds[..]];
+        mint_to_checked(
+            CpiContext::new_with_signer(
+                self.token2022_program.to_account_info(),
+                MintToChecked {
+                    mint: self.shares_mint.to_account_info(),
+                    authority: self.shares_mint.to_account_info(),
+                    to: fee_recipient_shares_vault.to_account_info(),
+                },
+                signer_seeds,
+            ),
+            amount,
+            self.shares_mint.decimals,
+        )?;
+        Ok(())
+    }
+}
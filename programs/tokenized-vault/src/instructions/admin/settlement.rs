@@ -0,0 +1,22 @@
+use anchor_lang::prelude::*;
+
+use crate::states::{Access, Config};
+use crate::utils::{ACCESS_SEED, CONFIG_SEED};
+
+#[derive(Accounts)]
+pub struct UpdateSettlement<'info> {
+    pub authority: Signer<'info>,
+    #[account(seeds = [ACCESS_SEED.as_bytes(), authority.key().as_ref()], bump)]
+    pub access: Option<AccountLoader<'info, Access>>,
+    #[account(mut, seeds = [CONFIG_SEED.as_bytes()], bump)]
+    pub config: AccountLoader<'info, Config>,
+}
+
+pub fn process_set_cancellations_locked(
+    ctx: Context<UpdateSettlement>,
+    locked: bool,
+) -> Result<()> {
+    let config = &mut ctx.accounts.config.load_mut()?;
+    config.set_cancellations_locked(locked)?;
+    Ok(())
+}
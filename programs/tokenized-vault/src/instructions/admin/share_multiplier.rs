@@ -0,0 +1,72 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::program::invoke_signed;
+use anchor_spl::token_2022::spl_token_2022::extension::scaled_ui_amount;
+use anchor_spl::token_interface::{Mint, Token2022, TokenAccount, TokenInterface};
+
+use crate::states::{Access, Config, ShareMultiplierUpdated};
+use crate::utils::{share_ui_multiplier, ACCESS_SEED, CONFIG_SEED, SHARES_MINT_SEED};
+
+#[derive(Accounts)]
+pub struct UpdateShareMultiplier<'info> {
+    pub authority: Signer<'info>,
+    #[account(seeds = [ACCESS_SEED.as_bytes(), authority.key().as_ref()], bump)]
+    pub access: Option<AccountLoader<'info, Access>>,
+    #[account(seeds = [CONFIG_SEED.as_bytes()], bump, has_one = asset_mint)]
+    pub config: AccountLoader<'info, Config>,
+
+    #[account(mint::token_program = asset_token_program)]
+    pub asset_mint: InterfaceAccount<'info, Mint>,
+    #[account(associated_token::mint = asset_mint, associated_token::authority = shares_mint, associated_token::token_program = asset_token_program)]
+    pub asset_vault: InterfaceAccount<'info, TokenAccount>,
+    #[account(mut, seeds = [SHARES_MINT_SEED.as_bytes()], bump, mint::token_program = token2022_program)]
+    pub shares_mint: InterfaceAccount<'info, Mint>,
+
+    pub asset_token_program: Interface<'info, TokenInterface>,
+    pub token2022_program: Program<'info, Token2022>,
+}
+
+pub fn process_update_share_multiplier(ctx: Context<UpdateShareMultiplier>) -> Result<()> {
+    let config = ctx.accounts.config.load()?;
+    let effective_timestamp = Clock::get()?.unix_timestamp;
+    let multiplier = share_ui_multiplier(
+        config.total_assets(ctx.accounts.asset_vault.amount),
+        config.total_supply(ctx.accounts.shares_mint.supply, effective_timestamp),
+        config.decimals_offset,
+    );
+    ctx.accounts
+        .update_multiplier(ctx.bumps.shares_mint, multiplier, effective_timestamp)?;
+    emit!(ShareMultiplierUpdated {
+        multiplier,
+        effective_timestamp,
+    });
+    Ok(())
+}
+
+impl UpdateShareMultiplier<'_> {
+    pub fn update_multiplier(
+        &self,
+        bump: u8,
+        multiplier: f64,
+        effective_timestamp: i64,
+    ) -> Result<()> {
+        let seeds = &[SHARES_MINT_SEED.as_bytes(), &[bump]];
+        let signer_seeds = &[&seeds[..]];
+        let ix = scaled_ui_amount::instruction::update_multiplier(
+            self.token2022_program.key,
+            &self.shares_mint.key(),
+            &self.shares_mint.key(),
+            &[],
+            multiplier,
+            effective_timestamp,
+        )?;
+        invoke_signed(
+            &ix,
+            &[
+                self.shares_mint.to_account_info(),
+                self.shares_mint.to_account_info(),
+            ],
+            signer_seeds,
+        )?;
+        Ok(())
+    }
+}
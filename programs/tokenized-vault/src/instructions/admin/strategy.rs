@@ -0,0 +1,240 @@
+use anchor_lang::prelude::*;
+
+use anchor_spl::token_interface::{
+    transfer_checked, Mint, TokenAccount, TokenInterface, TransferChecked,
+};
+
+use strategy_interface::{onchain, StrategyInstruction};
+
+use crate::error::ErrorCode;
+use crate::states::{Access, Config, DebtUpdated, Strategy, StrategyRemoved};
+use crate::utils::{amount_after_fee, ACCESS_SEED, CONFIG_SEED, SHARES_MINT_SEED, STRATEGY_SEED};
+
+#[derive(Accounts)]
+pub struct AddStrategy<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    #[account(seeds = [ACCESS_SEED.as_bytes(), authority.key().as_ref()], bump)]
+    pub access: Option<AccountLoader<'info, Access>>,
+    #[account(seeds = [CONFIG_SEED.as_bytes()], bump, has_one = asset_mint)]
+    pub config: AccountLoader<'info, Config>,
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + Strategy::INIT_SPACE,
+        seeds = [STRATEGY_SEED.as_bytes(), config.key().as_ref(), strategy_program.key().as_ref()],
+        bump
+    )]
+    pub strategy: AccountLoader<'info, Strategy>,
+    /// CHECK: Program receiving the strategy deposits and withdrawals
+    #[account(executable)]
+    pub strategy_program: UncheckedAccount<'info>,
+
+    #[account(mint::token_program = asset_token_program)]
+    pub asset_mint: InterfaceAccount<'info, Mint>,
+    #[account(token::mint = asset_mint, token::token_program = asset_token_program)]
+    pub strategy_asset_account: InterfaceAccount<'info, TokenAccount>,
+
+    pub asset_token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateStrategy<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    #[account(seeds = [ACCESS_SEED.as_bytes(), authority.key().as_ref()], bump)]
+    pub access: Option<AccountLoader<'info, Access>>,
+    #[account(seeds = [CONFIG_SEED.as_bytes()], bump)]
+    pub config: AccountLoader<'info, Config>,
+    #[account(
+        mut,
+        seeds = [STRATEGY_SEED.as_bytes(), config.key().as_ref(), strategy_program.key().as_ref()],
+        bump,
+        has_one = strategy_program
+    )]
+    pub strategy: AccountLoader<'info, Strategy>,
+    /// CHECK: Matched against the strategy account
+    pub strategy_program: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateDebt<'info> {
+    pub authority: Signer<'info>,
+    #[account(seeds = [ACCESS_SEED.as_bytes(), authority.key().as_ref()], bump)]
+    pub access: Option<AccountLoader<'info, Access>>,
+    #[account(mut, seeds = [CONFIG_SEED.as_bytes()], bump, has_one = asset_mint)]
+    pub config: AccountLoader<'info, Config>,
+    #[account(
+        mut,
+        seeds = [STRATEGY_SEED.as_bytes(), config.key().as_ref(), strategy_program.key().as_ref()],
+        bump,
+        has_one = strategy_program
+    )]
+    pub strategy: AccountLoader<'info, Strategy>,
+    /// CHECK: Matched against the strategy account
+    #[account(executable)]
+    pub strategy_program: UncheckedAccount<'info>,
+
+    #[account(mint::token_program = asset_token_program)]
+    pub asset_mint: InterfaceAccount<'info, Mint>,
+    #[account(mut, associated_token::mint = asset_mint, associated_token::authority = shares_mint, associated_token::token_program = asset_token_program)]
+    pub asset_vault: InterfaceAccount<'info, TokenAccount>,
+    #[account(mut, address = strategy.load()?.asset_account)]
+    pub strategy_asset_account: InterfaceAccount<'info, TokenAccount>,
+    /// CHECK: Authority of the asset vault
+    #[account(seeds = [SHARES_MINT_SEED.as_bytes()], bump)]
+    pub shares_mint: UncheckedAccount<'info>,
+
+    pub asset_token_program: Interface<'info, TokenInterface>,
+}
+
+pub fn process_add_strategy(ctx: Context<AddStrategy>, max_debt: u64) -> Result<()> {
+    ctx.accounts.strategy.load_init()?.initialize(
+        ctx.accounts.config.key(),
+        ctx.accounts.strategy_program.key(),
+        ctx.accounts.strategy_asset_account.key(),
+        max_debt,
+        Clock::get()?.unix_timestamp,
+    )
+}
+
+pub fn process_remove_strategy(ctx: Context<UpdateStrategy>) -> Result<()> {
+    if ctx.accounts.strategy.load()?.current_debt > 0 {
+        return Err(ErrorCode::StrategyHasDebt.into());
+    }
+    ctx.accounts
+        .strategy
+        .close(ctx.accounts.authority.to_account_info())?;
+    emit!(StrategyRemoved {
+        strategy_program: ctx.accounts.strategy_program.key(),
+    });
+    Ok(())
+}
+
+pub fn process_update_max_debt(ctx: Context<UpdateStrategy>, max_debt: u64) -> Result<()> {
+    ctx.accounts.strategy.load_mut()?.set_max_debt(max_debt)
+}
+
+/// Moves assets between the vault and the strategy towards `target_debt`, capped by the
+/// strategy's max debt on the way up and by the idle assets available to deposit.
+pub fn process_update_debt<'info>(
+    ctx: Context<'_, '_, 'info, 'info, UpdateDebt<'info>>,
+    target_debt: u64,
+) -> Result<()> {
+    let (current_debt, max_debt) = {
+        let strategy = ctx.accounts.strategy.load()?;
+        (strategy.current_debt, strategy.max_debt)
+    };
+    let target_debt = target_debt.min(max_debt);
+    let new_debt = if target_debt > current_debt {
+        let idle = ctx
+            .accounts
+            .config
+            .load()?
+            .idle_assets(ctx.accounts.asset_vault.amount);
+        let assets = (target_debt - current_debt).min(idle);
+        if assets == 0 {
+            return Err(ErrorCode::InsufficientIdleAssets.into());
+        }
+        ctx.accounts
+            .transfer_assets_to_strategy(ctx.bumps.shares_mint, assets)?;
+        // The strategy owes what it received, net of the asset's transfer fee
+        let received = amount_after_fee(&ctx.accounts.asset_mint.to_account_info(), assets)?;
+        ctx.accounts.invoke_strategy(
+            ctx.bumps.strategy,
+            &StrategyInstruction::Deposit { amount: received },
+            ctx.remaining_accounts,
+        )?;
+        current_debt + received
+    } else if target_debt < current_debt {
+        let assets = current_debt - target_debt;
+        let balance_before = ctx.accounts.asset_vault.amount;
+        ctx.accounts.invoke_strategy(
+            ctx.bumps.strategy,
+            &StrategyInstruction::Withdraw { amount: assets },
+            ctx.remaining_accounts,
+        )?;
+        ctx.accounts.asset_vault.reload()?;
+        let received = ctx
+            .accounts
+            .asset_vault
+            .amount
+            .saturating_sub(balance_before);
+        if received < amount_after_fee(&ctx.accounts.asset_mint.to_account_info(), assets)? {
+            return Err(ErrorCode::StrategyWithdrawShortfall.into());
+        }
+        target_debt
+    } else {
+        return Err(ErrorCode::DebtUnchanged.into());
+    };
+
+    let mut config = ctx.accounts.config.load_mut()?;
+    config.total_debt = config
+        .total_debt
+        .saturating_sub(current_debt)
+        .saturating_add(new_debt);
+    ctx.accounts.strategy.load_mut()?.current_debt = new_debt;
+    emit!(DebtUpdated {
+        strategy_program: ctx.accounts.strategy_program.key(),
+        current_debt,
+        new_debt,
+    });
+    Ok(())
+}
+
+impl<'info> UpdateDebt<'info> {
+    pub fn transfer_assets_to_strategy(&self, bump: u8, amount: u64) -> Result<()> {
+        let seeds = &[SHARES_MINT_SEED.as_bytes(), &[bump]];
+        let signer_seeds = &[&seeds[..]];
+        transfer_checked(
+            CpiContext::new_with_signer(
+                self.asset_token_program.to_account_info(),
+                TransferChecked {
+                    from: self.asset_vault.to_account_info(),
+                    to: self.strategy_asset_account.to_account_info(),
+                    mint: self.asset_mint.to_account_info(),
+                    authority: self.shares_mint.to_account_info(),
+                },
+                signer_seeds,
+            ),
+            amount,
+            self.asset_mint.decimals,
+        )?;
+        Ok(())
+    }
+
+    /// Calls the strategy program signed by the `Strategy` PDA, which authenticates the
+    /// vault without handing over the authority of the asset vault or the shares mint.
+    pub fn invoke_strategy(
+        &self,
+        bump: u8,
+        instruction: &StrategyInstruction,
+        remaining_accounts: &[AccountInfo<'info>],
+    ) -> Result<()> {
+        let config = self.config.key();
+        let strategy_program = self.strategy_program.key();
+        let seeds = &[
+            STRATEGY_SEED.as_bytes(),
+            config.as_ref(),
+            strategy_program.as_ref(),
+            &[bump],
+        ];
+        let mut accounts = vec![
+            (self.strategy.to_account_info(), false),
+            (self.strategy_asset_account.to_account_info(), true),
+        ];
+        if matches!(instruction, StrategyInstruction::Withdraw { .. }) {
+            accounts.push((self.asset_vault.to_account_info(), true));
+        }
+        accounts.push((self.asset_mint.to_account_info(), false));
+        accounts.push((self.asset_token_program.to_account_info(), false));
+        onchain::invoke(
+            &self.strategy_program.to_account_info(),
+            instruction,
+            &accounts,
+            remaining_accounts,
+            &[&seeds[..]],
+        )
+    }
+}
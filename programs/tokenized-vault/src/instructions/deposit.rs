@@ -6,19 +6,28 @@ use anchor_spl::token_interface::{
     TokenInterface, TransferChecked,
 };
 
-use crate::states::Config;
+use crate::error::ErrorCode;
+use crate::states::{Access, Config, UserPosition};
 use crate::utils::{
-    preview_deposit_internal, preview_mint_internal, CONFIG_SEED, SHARES_MINT_SEED,
+    amount_after_fee, amount_before_fee, is_owner_or_delegate, preview_deposit_internal,
+    preview_mint_internal, ACCESS_SEED, BLOCKED_SEED, CONFIG_SEED, SHARES_MINT_SEED,
+    USER_POSITION_SEED,
 };
 
 #[derive(Accounts)]
 pub struct Deposit<'info> {
     #[account(mut)]
     pub user: Signer<'info>,
+    #[account(seeds = [ACCESS_SEED.as_bytes(), user.key().as_ref()], bump)]
+    pub user_access: Option<AccountLoader<'info, Access>>,
+    /// CHECK: Blocked PDA of the user, must not be initialized
+    #[account(seeds = [BLOCKED_SEED.as_bytes(), user.key().as_ref()], bump)]
+    pub user_blocked: UncheckedAccount<'info>,
 
     #[account(mut, mint::token_program = asset_token_program)]
     pub asset_mint: InterfaceAccount<'info, Mint>,
-    #[account(mut, associated_token::mint = asset_mint, associated_token::authority = user, associated_token::token_program = asset_token_program)]
+    /// Any asset token account owned by the user, or delegated to the user
+    #[account(mut, token::mint = asset_mint, token::token_program = asset_token_program, constraint = is_owner_or_delegate(&user_asset_vault, &user.key()) @ ErrorCode::InvalidTokenAccountAuthority)]
     pub user_asset_vault: InterfaceAccount<'info, TokenAccount>,
 
     #[account(mut, associated_token::mint = asset_mint, associated_token::authority = shares_mint, associated_token::token_program = asset_token_program)]
@@ -31,6 +40,14 @@ pub struct Deposit<'info> {
 
     #[account(seeds = [CONFIG_SEED.as_bytes()], bump, has_one = asset_mint)]
     pub config: AccountLoader<'info, Config>,
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = 8 + UserPosition::INIT_SPACE,
+        seeds = [USER_POSITION_SEED.as_bytes(), config.key().as_ref(), user.key().as_ref()],
+        bump
+    )]
+    pub user_position: AccountLoader<'info, UserPosition>,
 
     pub asset_token_program: Interface<'info, TokenInterface>,
     pub token2022_program: Program<'info, Token2022>,
@@ -40,28 +57,57 @@ pub struct Deposit<'info> {
 }
 
 pub fn process_deposit(ctx: Context<Deposit>, assets: u64) -> Result<()> {
+    let config = ctx.accounts.config.load()?;
+    config.check_sync_deposits()?;
+    let clock = Clock::get()?;
+    config.check_report_slot(clock.slot)?;
+    let total_assets = config.total_assets(ctx.accounts.asset_vault.amount);
+    // Shares are minted against what the vault receives net of transfer fees
+    let received = amount_after_fee(&ctx.accounts.asset_mint.to_account_info(), assets)?;
+    config.check_deposit_cap(total_assets, received)?;
+    let mut user_position = UserPosition::load_or_init(
+        &ctx.accounts.user_position,
+        ctx.accounts.config.key(),
+        ctx.accounts.user.key(),
+    )?;
+    user_position.check_deposit_limit(config.default_deposit_limit(), received)?;
     let shares = preview_deposit_internal(
-        assets,
-        ctx.accounts.asset_vault.amount,
-        ctx.accounts.shares_mint.supply,
-        ctx.accounts.config.load()?.decimals_offset,
+        received,
+        total_assets,
+        config.total_supply(ctx.accounts.shares_mint.supply, clock.unix_timestamp),
+        config.decimals_offset,
     )?;
     ctx.accounts.transfer_assets_from_user_to_vault(assets)?;
     ctx.accounts
         .mint_shares_to_user(ctx.bumps.shares_mint, shares)?;
+    user_position.record_deposit(received, &clock)?;
     Ok(())
 }
 
 pub fn process_mint(ctx: Context<Deposit>, shares: u64) -> Result<()> {
+    let config = ctx.accounts.config.load()?;
+    config.check_sync_deposits()?;
+    let clock = Clock::get()?;
+    config.check_report_slot(clock.slot)?;
+    let total_assets = config.total_assets(ctx.accounts.asset_vault.amount);
     let assets = preview_mint_internal(
         shares,
-        ctx.accounts.asset_vault.amount,
-        ctx.accounts.shares_mint.supply,
-        ctx.accounts.config.load()?.decimals_offset,
+        total_assets,
+        config.total_supply(ctx.accounts.shares_mint.supply, clock.unix_timestamp),
+        config.decimals_offset,
     )?;
-    ctx.accounts.transfer_assets_from_user_to_vault(assets)?;
+    config.check_deposit_cap(total_assets, assets)?;
+    let mut user_position = UserPosition::load_or_init(
+        &ctx.accounts.user_position,
+        ctx.accounts.config.key(),
+        ctx.accounts.user.key(),
+    )?;
+    user_position.check_deposit_limit(config.default_deposit_limit(), assets)?;
+    let amount = amount_before_fee(&ctx.accounts.asset_mint.to_account_info(), assets)?;
+    ctx.accounts.transfer_assets_from_user_to_vault(amount)?;
     ctx.accounts
         .mint_shares_to_user(ctx.bumps.shares_mint, shares)?;
+    user_position.record_deposit(assets, &clock)?;
     Ok(())
 }
 
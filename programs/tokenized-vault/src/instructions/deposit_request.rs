@@ -0,0 +1,357 @@
+use anchor_lang::prelude::*;
+
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token_interface::{
+    mint_to_checked, transfer_checked, Mint, MintToChecked, Token2022, TokenAccount,
+    TokenInterface, TransferChecked,
+};
+
+use crate::error::ErrorCode;
+use crate::states::{Access, Config, DepositEpoch, DepositRequest, UserPosition};
+use crate::utils::{
+    amount_after_fee, preview_deposit_internal, transfer_shares_checked, ACCESS_SEED, BLOCKED_SEED,
+    CONFIG_SEED, DEPOSIT_EPOCH_SEED, DEPOSIT_ESCROW_SEED, DEPOSIT_REQUEST_SEED, SHARES_MINT_SEED,
+    USER_POSITION_SEED,
+};
+
+#[derive(Accounts)]
+pub struct RequestDeposit<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+    #[account(seeds = [ACCESS_SEED.as_bytes(), user.key().as_ref()], bump)]
+    pub user_access: Option<AccountLoader<'info, Access>>,
+    /// CHECK: Blocked PDA of the user, must not be initialized
+    #[account(seeds = [BLOCKED_SEED.as_bytes(), user.key().as_ref()], bump)]
+    pub user_blocked: UncheckedAccount<'info>,
+
+    #[account(mint::token_program = asset_token_program)]
+    pub asset_mint: InterfaceAccount<'info, Mint>,
+    #[account(mut, associated_token::mint = asset_mint, associated_token::authority = user, associated_token::token_program = asset_token_program)]
+    pub user_asset_vault: InterfaceAccount<'info, TokenAccount>,
+    #[account(mut, associated_token::mint = asset_mint, associated_token::authority = shares_mint, associated_token::token_program = asset_token_program)]
+    pub asset_vault: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(seeds = [SHARES_MINT_SEED.as_bytes()], bump, mint::token_program = token2022_program)]
+    pub shares_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(mut, seeds = [CONFIG_SEED.as_bytes()], bump, has_one = asset_mint)]
+    pub config: AccountLoader<'info, Config>,
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = 8 + UserPosition::INIT_SPACE,
+        seeds = [USER_POSITION_SEED.as_bytes(), config.key().as_ref(), user.key().as_ref()],
+        bump
+    )]
+    pub user_position: AccountLoader<'info, UserPosition>,
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = 8 + DepositRequest::INIT_SPACE,
+        seeds = [DEPOSIT_REQUEST_SEED.as_bytes(), config.key().as_ref(), user.key().as_ref()],
+        bump
+    )]
+    pub deposit_request: AccountLoader<'info, DepositRequest>,
+
+    pub asset_token_program: Interface<'info, TokenInterface>,
+    pub token2022_program: Program<'info, Token2022>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+#[derive(Accounts)]
+pub struct SettleDeposits<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    #[account(seeds = [ACCESS_SEED.as_bytes(), authority.key().as_ref()], bump)]
+    pub access: Option<AccountLoader<'info, Access>>,
+
+    #[account(mint::token_program = asset_token_program)]
+    pub asset_mint: InterfaceAccount<'info, Mint>,
+    #[account(associated_token::mint = asset_mint, associated_token::authority = shares_mint, associated_token::token_program = asset_token_program)]
+    pub asset_vault: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(mut, seeds = [SHARES_MINT_SEED.as_bytes()], bump, mint::token_program = token2022_program)]
+    pub shares_mint: InterfaceAccount<'info, Mint>,
+    #[account(
+        init_if_needed,
+        payer = authority,
+        seeds = [DEPOSIT_ESCROW_SEED.as_bytes()],
+        bump,
+        token::mint = shares_mint,
+        token::authority = shares_mint,
+        token::token_program = token2022_program
+    )]
+    pub deposit_escrow: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(mut, seeds = [CONFIG_SEED.as_bytes()], bump, has_one = asset_mint)]
+    pub config: AccountLoader<'info, Config>,
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + DepositEpoch::INIT_SPACE,
+        seeds = [DEPOSIT_EPOCH_SEED.as_bytes(), config.key().as_ref(), &config.load()?.deposit_epoch.to_le_bytes()],
+        bump
+    )]
+    pub deposit_epoch: AccountLoader<'info, DepositEpoch>,
+
+    pub asset_token_program: Interface<'info, TokenInterface>,
+    pub token2022_program: Program<'info, Token2022>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+#[derive(Accounts)]
+pub struct ClaimDeposit<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+    /// CHECK: Blocked PDA of the user, must not be initialized
+    #[account(seeds = [BLOCKED_SEED.as_bytes(), user.key().as_ref()], bump)]
+    pub user_blocked: UncheckedAccount<'info>,
+
+    #[account(seeds = [SHARES_MINT_SEED.as_bytes()], bump, mint::token_program = token2022_program)]
+    pub shares_mint: InterfaceAccount<'info, Mint>,
+    #[account(init_if_needed, payer = user, associated_token::mint = shares_mint, associated_token::authority = user, associated_token::token_program = token2022_program)]
+    pub user_shares_vault: InterfaceAccount<'info, TokenAccount>,
+    #[account(mut, seeds = [DEPOSIT_ESCROW_SEED.as_bytes()], bump)]
+    pub deposit_escrow: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(seeds = [CONFIG_SEED.as_bytes()], bump)]
+    pub config: AccountLoader<'info, Config>,
+    #[account(
+        mut,
+        close = user,
+        seeds = [DEPOSIT_REQUEST_SEED.as_bytes(), config.key().as_ref(), user.key().as_ref()],
+        bump
+    )]
+    pub deposit_request: AccountLoader<'info, DepositRequest>,
+    #[account(
+        mut,
+        seeds = [DEPOSIT_EPOCH_SEED.as_bytes(), config.key().as_ref(), &deposit_request.load()?.epoch.to_le_bytes()],
+        bump
+    )]
+    pub deposit_epoch: AccountLoader<'info, DepositEpoch>,
+
+    pub token2022_program: Program<'info, Token2022>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct CancelDepositRequest<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+    /// CHECK: Blocked PDA of the user, must not be initialized
+    #[account(seeds = [BLOCKED_SEED.as_bytes(), user.key().as_ref()], bump)]
+    pub user_blocked: UncheckedAccount<'info>,
+
+    #[account(mint::token_program = asset_token_program)]
+    pub asset_mint: InterfaceAccount<'info, Mint>,
+    #[account(mut, associated_token::mint = asset_mint, associated_token::authority = user, associated_token::token_program = asset_token_program)]
+    pub user_asset_vault: InterfaceAccount<'info, TokenAccount>,
+    #[account(mut, associated_token::mint = asset_mint, associated_token::authority = shares_mint, associated_token::token_program = asset_token_program)]
+    pub asset_vault: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(seeds = [SHARES_MINT_SEED.as_bytes()], bump, mint::token_program = token2022_program)]
+    pub shares_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(mut, seeds = [CONFIG_SEED.as_bytes()], bump, has_one = asset_mint)]
+    pub config: AccountLoader<'info, Config>,
+    #[account(
+        mut,
+        seeds = [USER_POSITION_SEED.as_bytes(), config.key().as_ref(), user.key().as_ref()],
+        bump
+    )]
+    pub user_position: AccountLoader<'info, UserPosition>,
+    #[account(
+        mut,
+        close = user,
+        seeds = [DEPOSIT_REQUEST_SEED.as_bytes(), config.key().as_ref(), user.key().as_ref()],
+        bump
+    )]
+    pub deposit_request: AccountLoader<'info, DepositRequest>,
+
+    pub asset_token_program: Interface<'info, TokenInterface>,
+    pub token2022_program: Program<'info, Token2022>,
+}
+
+pub fn process_request_deposit(ctx: Context<RequestDeposit>, assets: u64) -> Result<()> {
+    let mut config = ctx.accounts.config.load_mut()?;
+    config.check_async_deposits()?;
+    let total_assets = config
+        .total_assets(ctx.accounts.asset_vault.amount)
+        .saturating_add(config.pending_deposit_assets);
+    let received = amount_after_fee(&ctx.accounts.asset_mint.to_account_info(), assets)?;
+    config.check_deposit_cap(total_assets, received)?;
+    let mut user_position = UserPosition::load_or_init(
+        &ctx.accounts.user_position,
+        ctx.accounts.config.key(),
+        ctx.accounts.user.key(),
+    )?;
+    user_position.check_deposit_limit(config.default_deposit_limit(), received)?;
+    let mut deposit_request = DepositRequest::load_or_init(
+        &ctx.accounts.deposit_request,
+        ctx.accounts.config.key(),
+        ctx.accounts.user.key(),
+    )?;
+    deposit_request.request(received, config.deposit_epoch)?;
+    ctx.accounts.transfer_assets_from_user_to_vault(assets)?;
+    config.pending_deposit_assets = config.pending_deposit_assets.saturating_add(received);
+    user_position.record_deposit(received, &Clock::get()?)?;
+    Ok(())
+}
+
+pub fn process_settle_deposits(ctx: Context<SettleDeposits>) -> Result<()> {
+    let mut config = ctx.accounts.config.load_mut()?;
+    let assets = config.pending_deposit_assets;
+    if assets == 0 {
+        return Err(ErrorCode::NoPendingDeposits.into());
+    }
+    let now = Clock::get()?.unix_timestamp;
+    // Pending assets are already in the vault, net of the transfer fee, but not yet idle
+    let shares = preview_deposit_internal(
+        assets,
+        config.total_assets(ctx.accounts.asset_vault.amount),
+        config.total_supply(ctx.accounts.shares_mint.supply, now),
+        config.decimals_offset,
+    )?;
+    ctx.accounts
+        .mint_shares_to_escrow(ctx.bumps.shares_mint, shares)?;
+    ctx.accounts.deposit_epoch.load_init()?.settle(
+        ctx.accounts.config.key(),
+        config.deposit_epoch,
+        assets,
+        shares,
+        now,
+    )?;
+    config.deposit_epoch += 1;
+    config.pending_deposit_assets = 0;
+    Ok(())
+}
+
+pub fn process_claim_deposit<'info>(
+    ctx: Context<'_, '_, 'info, 'info, ClaimDeposit<'info>>,
+) -> Result<()> {
+    let config = ctx.accounts.config.load()?;
+    let deposit_epoch = &mut ctx.accounts.deposit_epoch.load_mut()?;
+    let shares = ctx
+        .accounts
+        .deposit_request
+        .load_mut()?
+        .claim(config.deposit_epoch, deposit_epoch)?;
+    ctx.accounts.transfer_shares_from_escrow_to_user(
+        ctx.bumps.shares_mint,
+        ctx.remaining_accounts,
+        shares,
+    )?;
+    Ok(())
+}
+
+pub fn process_cancel_deposit_request(ctx: Context<CancelDepositRequest>) -> Result<()> {
+    let mut config = ctx.accounts.config.load_mut()?;
+    config.check_cancellations_unlocked()?;
+    let assets = ctx
+        .accounts
+        .deposit_request
+        .load_mut()?
+        .cancel(config.deposit_epoch)?;
+    ctx.accounts
+        .transfer_assets_from_vault_to_user(ctx.bumps.shares_mint, assets)?;
+    config.pending_deposit_assets = config.pending_deposit_assets.saturating_sub(assets);
+    ctx.accounts
+        .user_position
+        .load_mut()?
+        .record_withdrawal(assets)?;
+    Ok(())
+}
+
+impl<'info> RequestDeposit<'info> {
+    pub fn transfer_assets_from_user_to_vault(&self, amount: u64) -> Result<()> {
+        transfer_checked(
+            CpiContext::new(
+                self.asset_token_program.to_account_info(),
+                TransferChecked {
+                    from: self.user_asset_vault.to_account_info(),
+                    to: self.asset_vault.to_account_info(),
+                    mint: self.asset_mint.to_account_info(),
+                    authority: self.user.to_account_info(),
+                },
+            ),
+            amount,
+            self.asset_mint.decimals,
+        )?;
+        Ok(())
+    }
+}
+
+impl<'info> SettleDeposits<'info> {
+    pub fn mint_shares_to_escrow(&self, bump: u8, amount: u64) -> Result<()> {
+        let seeds = &[SHARES_MINT_SEED.as_bytes(), &[bump]];
+        let signer_seeds = &[&seeds[..]];
+        mint_to_checked(
+            CpiContext::new_with_signer(
+                self.token2022_program.to_account_info(),
+                MintToChecked {
+                    mint: self.shares_mint.to_account_info(),
+                    authority: self.shares_mint.to_account_info(),
+                    to: self.deposit_escrow.to_account_info(),
+                },
+                signer_seeds,
+            ),
+            amount,
+            self.shares_mint.decimals,
+        )?;
+        Ok(())
+    }
+}
+
+impl<'info> CancelDepositRequest<'info> {
+    pub fn transfer_assets_from_vault_to_user(&self, bump: u8, amount: u64) -> Result<()> {
+        let seeds = &[SHARES_MINT_SEED.as_bytes(), &[bump]];
+        let signer_seeds = &[&seeds[..]];
+        transfer_checked(
+            CpiContext::new_with_signer(
+                self.asset_token_program.to_account_info(),
+                TransferChecked {
+                    from: self.asset_vault.to_account_info(),
+                    to: self.user_asset_vault.to_account_info(),
+                    mint: self.asset_mint.to_account_info(),
+                    authority: self.shares_mint.to_account_info(),
+                },
+                signer_seeds,
+            ),
+            amount,
+            self.asset_mint.decimals,
+        )?;
+        Ok(())
+    }
+}
+
+impl<'info> ClaimDeposit<'info> {
+    pub fn transfer_shares_from_escrow_to_user(
+        &self,
+        bump: u8,
+        remaining_accounts: &[AccountInfo<'info>],
+        amount: u64,
+    ) -> Result<()> {
+        let seeds = &[SHARES_MINT_SEED.as_bytes(), &[bump]];
+        let signer_seeds = &[&seeds[..]];
+        transfer_shares_checked(
+            CpiContext::new_with_signer(
+                self.token2022_program.to_account_info(),
+                TransferChecked {
+                    from: self.deposit_escrow.to_account_info(),
+                    to: self.user_shares_vault.to_account_info(),
+                    mint: self.shares_mint.to_account_info(),
+                    authority: self.shares_mint.to_account_info(),
+                },
+                signer_seeds,
+            )
+            .with_remaining_accounts(remaining_accounts.to_vec()),
+            amount,
+            self.shares_mint.decimals,
+        )?;
+        Ok(())
+    }
+}
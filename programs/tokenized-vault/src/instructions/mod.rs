@@ -6,3 +6,12 @@ pub use deposit::*;
 
 pub mod withdraw;
 pub use withdraw::*;
+
+pub mod redeem_request;
+pub use redeem_request::*;
+
+pub mod deposit_request;
+pub use deposit_request::*;
+
+pub mod native;
+pub use native::*;
@@ -0,0 +1,244 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{transfer, Transfer};
+
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token::spl_token::native_mint;
+use anchor_spl::token_interface::{
+    burn_checked, close_account, mint_to_checked, sync_native, transfer_checked, BurnChecked,
+    CloseAccount, Mint, MintToChecked, SyncNative, Token2022, TokenAccount, TokenInterface,
+    TransferChecked,
+};
+
+use crate::error::ErrorCode;
+use crate::states::{Access, Config, UserPosition};
+use crate::utils::{
+    preview_deposit_internal, preview_redeem_internal, ACCESS_SEED, BLOCKED_SEED, CONFIG_SEED,
+    SHARES_MINT_SEED, UNWRAP_SEED, USER_POSITION_SEED,
+};
+
+#[derive(Accounts)]
+pub struct DepositSol<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+    #[account(seeds = [ACCESS_SEED.as_bytes(), user.key().as_ref()], bump)]
+    pub user_access: Option<AccountLoader<'info, Access>>,
+    /// CHECK: Blocked PDA of the user, must not be initialized
+    #[account(seeds = [BLOCKED_SEED.as_bytes(), user.key().as_ref()], bump)]
+    pub user_blocked: UncheckedAccount<'info>,
+
+    #[account(address = native_mint::ID @ ErrorCode::NotNativeMint, mint::token_program = asset_token_program)]
+    pub asset_mint: InterfaceAccount<'info, Mint>,
+    #[account(mut, associated_token::mint = asset_mint, associated_token::authority = shares_mint, associated_token::token_program = asset_token_program)]
+    pub asset_vault: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(mut, seeds = [SHARES_MINT_SEED.as_bytes()], bump, mint::token_program = token2022_program)]
+    pub shares_mint: InterfaceAccount<'info, Mint>,
+    #[account(init_if_needed, payer = user, associated_token::mint = shares_mint, associated_token::authority = user, associated_token::token_program = token2022_program)]
+    pub user_shares_vault: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(seeds = [CONFIG_SEED.as_bytes()], bump, has_one = asset_mint)]
+    pub config: AccountLoader<'info, Config>,
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = 8 + UserPosition::INIT_SPACE,
+        seeds = [USER_POSITION_SEED.as_bytes(), config.key().as_ref(), user.key().as_ref()],
+        bump
+    )]
+    pub user_position: AccountLoader<'info, UserPosition>,
+
+    pub asset_token_program: Interface<'info, TokenInterface>,
+    pub token2022_program: Program<'info, Token2022>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+pub fn process_deposit_sol(ctx: Context<DepositSol>, lamports: u64) -> Result<()> {
+    let config = ctx.accounts.config.load()?;
+    config.check_sync_deposits()?;
+    let clock = Clock::get()?;
+    config.check_report_slot(clock.slot)?;
+    let total_assets = config.total_assets(ctx.accounts.asset_vault.amount);
+    config.check_deposit_cap(total_assets, lamports)?;
+    let mut user_position = UserPosition::load_or_init(
+        &ctx.accounts.user_position,
+        ctx.accounts.config.key(),
+        ctx.accounts.user.key(),
+    )?;
+    user_position.check_deposit_limit(config.default_deposit_limit(), lamports)?;
+    let shares = preview_deposit_internal(
+        lamports,
+        total_assets,
+        config.total_supply(ctx.accounts.shares_mint.supply, clock.unix_timestamp),
+        config.decimals_offset,
+    )?;
+    ctx.accounts.wrap_lamports_into_vault(lamports)?;
+    ctx.accounts
+        .mint_shares_to_user(ctx.bumps.shares_mint, shares)?;
+    user_position.record_deposit(lamports, &clock)?;
+    Ok(())
+}
+
+impl<'info> DepositSol<'info> {
+    /// Moves lamports into the wSOL asset vault and syncs its token balance.
+    pub fn wrap_lamports_into_vault(&self, lamports: u64) -> Result<()> {
+        transfer(
+            CpiContext::new(
+                self.system_program.to_account_info(),
+                Transfer {
+                    from: self.user.to_account_info(),
+                    to: self.asset_vault.to_account_info(),
+                },
+            ),
+            lamports,
+        )?;
+        sync_native(CpiContext::new(
+            self.asset_token_program.to_account_info(),
+            SyncNative {
+                account: self.asset_vault.to_account_info(),
+            },
+        ))?;
+        Ok(())
+    }
+    pub fn mint_shares_to_user(&self, bump: u8, amount: u64) -> Result<()> {
+        let seeds = &[SHARES_MINT_SEED.as_bytes(), &[bump]];
+        let signer_seeds = &[&seeds[..]];
+        mint_to_checked(
+            CpiContext::new_with_signer(
+                self.token2022_program.to_account_info(),
+                MintToChecked {
+                    mint: self.shares_mint.to_account_info(),
+                    authority: self.shares_mint.to_account_info(),
+                    to: self.user_shares_vault.to_account_info(),
+                },
+                signer_seeds,
+            ),
+            amount,
+            self.shares_mint.decimals,
+        )?;
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct WithdrawSol<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+    /// CHECK: Blocked PDA of the user, must not be initialized
+    #[account(seeds = [BLOCKED_SEED.as_bytes(), user.key().as_ref()], bump)]
+    pub user_blocked: UncheckedAccount<'info>,
+
+    #[account(address = native_mint::ID @ ErrorCode::NotNativeMint, mint::token_program = asset_token_program)]
+    pub asset_mint: InterfaceAccount<'info, Mint>,
+    #[account(mut, associated_token::mint = asset_mint, associated_token::authority = shares_mint, associated_token::token_program = asset_token_program)]
+    pub asset_vault: InterfaceAccount<'info, TokenAccount>,
+    /// Temporary wSOL account, closed into the user within the instruction
+    #[account(
+        init,
+        payer = user,
+        seeds = [UNWRAP_SEED.as_bytes(), user.key().as_ref()],
+        bump,
+        token::mint = asset_mint,
+        token::authority = shares_mint,
+        token::token_program = asset_token_program
+    )]
+    pub unwrap_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(mut, seeds = [SHARES_MINT_SEED.as_bytes()], bump, mint::token_program = token2022_program)]
+    pub shares_mint: InterfaceAccount<'info, Mint>,
+    #[account(mut, associated_token::mint = shares_mint, associated_token::authority = user, associated_token::token_program = token2022_program)]
+    pub user_shares_vault: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(mut, seeds = [CONFIG_SEED.as_bytes()], bump, has_one = asset_mint)]
+    pub config: AccountLoader<'info, Config>,
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = 8 + UserPosition::INIT_SPACE,
+        seeds = [USER_POSITION_SEED.as_bytes(), config.key().as_ref(), user.key().as_ref()],
+        bump
+    )]
+    pub user_position: AccountLoader<'info, UserPosition>,
+
+    pub asset_token_program: Interface<'info, TokenInterface>,
+    pub token2022_program: Program<'info, Token2022>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+pub fn process_withdraw_sol(ctx: Context<WithdrawSol>, shares: u64) -> Result<()> {
+    let mut config = ctx.accounts.config.load_mut()?;
+    let mut user_position = UserPosition::load_or_init(
+        &ctx.accounts.user_position,
+        ctx.accounts.config.key(),
+        ctx.accounts.user.key(),
+    )?;
+    let clock = Clock::get()?;
+    let now = clock.unix_timestamp;
+    user_position.check_cooldown(config.withdraw_cooldown, now)?;
+    user_position.check_same_slot(clock.slot)?;
+    config.check_withdraw_report_slot(clock.slot)?;
+    let total_assets = config.total_assets(ctx.accounts.asset_vault.amount);
+    let lamports = preview_redeem_internal(
+        shares,
+        total_assets,
+        config.total_supply(ctx.accounts.shares_mint.supply, now),
+        config.decimals_offset,
+    )?;
+    config.check_idle_assets(ctx.accounts.asset_vault.amount, lamports)?;
+    config.consume_withdraw_limit(total_assets, lamports, now)?;
+    ctx.accounts
+        .unwrap_lamports_to_user(ctx.bumps.shares_mint, lamports)?;
+    ctx.accounts.burn_shares_from_user(shares)?;
+    user_position.record_withdrawal(lamports)?;
+    Ok(())
+}
+
+impl<'info> WithdrawSol<'info> {
+    /// Moves wSOL into the temporary account and closes it, paying the lamports
+    /// (including its rent) to the user.
+    pub fn unwrap_lamports_to_user(&self, bump: u8, lamports: u64) -> Result<()> {
+        let seeds = &[SHARES_MINT_SEED.as_bytes(), &[bump]];
+        let signer_seeds = &[&seeds[..]];
+        transfer_checked(
+            CpiContext::new_with_signer(
+                self.asset_token_program.to_account_info(),
+                TransferChecked {
+                    from: self.asset_vault.to_account_info(),
+                    to: self.unwrap_account.to_account_info(),
+                    mint: self.asset_mint.to_account_info(),
+                    authority: self.shares_mint.to_account_info(),
+                },
+                signer_seeds,
+            ),
+            lamports,
+            self.asset_mint.decimals,
+        )?;
+        close_account(CpiContext::new_with_signer(
+            self.asset_token_program.to_account_info(),
+            CloseAccount {
+                account: self.unwrap_account.to_account_info(),
+                destination: self.user.to_account_info(),
+                authority: self.shares_mint.to_account_info(),
+            },
+            signer_seeds,
+        ))?;
+        Ok(())
+    }
+    pub fn burn_shares_from_user(&self, amount: u64) -> Result<()> {
+        burn_checked(
+            CpiContext::new(
+                self.token2022_program.to_account_info(),
+                BurnChecked {
+                    mint: self.shares_mint.to_account_info(),
+                    authority: self.user.to_account_info(),
+                    from: self.user_shares_vault.to_account_info(),
+                },
+            ),
+            amount,
+            self.shares_mint.decimals,
+        )?;
+        Ok(())
+    }
+}
@@ -0,0 +1,398 @@
+use anchor_lang::prelude::*;
+
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token_interface::{
+    burn_checked, transfer_checked, BurnChecked, Mint, Token2022, TokenAccount, TokenInterface,
+    TransferChecked,
+};
+
+use crate::error::ErrorCode;
+use crate::states::{Access, Config, RedeemRequest, UserPosition, WithdrawalEpoch};
+use crate::utils::{
+    preview_deposit_internal, preview_redeem_internal, transfer_shares_checked, ACCESS_SEED,
+    BLOCKED_SEED, CONFIG_SEED, REDEEM_ESCROW_SEED, REDEEM_REQUEST_SEED, SHARES_MINT_SEED,
+    USER_POSITION_SEED, WITHDRAWAL_EPOCH_SEED,
+};
+
+#[derive(Accounts)]
+pub struct RequestRedeem<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+    /// CHECK: Blocked PDA of the user, must not be initialized
+    #[account(seeds = [BLOCKED_SEED.as_bytes(), user.key().as_ref()], bump)]
+    pub user_blocked: UncheckedAccount<'info>,
+
+    #[account(seeds = [SHARES_MINT_SEED.as_bytes()], bump, mint::token_program = token2022_program)]
+    pub shares_mint: InterfaceAccount<'info, Mint>,
+    #[account(mut, associated_token::mint = shares_mint, associated_token::authority = user, associated_token::token_program = token2022_program)]
+    pub user_shares_vault: InterfaceAccount<'info, TokenAccount>,
+    #[account(
+        init_if_needed,
+        payer = user,
+        seeds = [REDEEM_ESCROW_SEED.as_bytes()],
+        bump,
+        token::mint = shares_mint,
+        token::authority = shares_mint,
+        token::token_program = token2022_program
+    )]
+    pub redeem_escrow: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(mut, seeds = [CONFIG_SEED.as_bytes()], bump)]
+    pub config: AccountLoader<'info, Config>,
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = 8 + UserPosition::INIT_SPACE,
+        seeds = [USER_POSITION_SEED.as_bytes(), config.key().as_ref(), user.key().as_ref()],
+        bump
+    )]
+    pub user_position: AccountLoader<'info, UserPosition>,
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = 8 + RedeemRequest::INIT_SPACE,
+        seeds = [REDEEM_REQUEST_SEED.as_bytes(), config.key().as_ref(), user.key().as_ref()],
+        bump
+    )]
+    pub redeem_request: AccountLoader<'info, RedeemRequest>,
+
+    pub token2022_program: Program<'info, Token2022>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+#[derive(Accounts)]
+pub struct CloseWithdrawalEpoch<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    #[account(seeds = [ACCESS_SEED.as_bytes(), authority.key().as_ref()], bump)]
+    pub access: Option<AccountLoader<'info, Access>>,
+
+    #[account(mint::token_program = asset_token_program)]
+    pub asset_mint: InterfaceAccount<'info, Mint>,
+    #[account(associated_token::mint = asset_mint, associated_token::authority = shares_mint, associated_token::token_program = asset_token_program)]
+    pub asset_vault: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(mut, seeds = [SHARES_MINT_SEED.as_bytes()], bump, mint::token_program = token2022_program)]
+    pub shares_mint: InterfaceAccount<'info, Mint>,
+    #[account(mut, seeds = [REDEEM_ESCROW_SEED.as_bytes()], bump)]
+    pub redeem_escrow: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(mut, seeds = [CONFIG_SEED.as_bytes()], bump, has_one = asset_mint)]
+    pub config: AccountLoader<'info, Config>,
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + WithdrawalEpoch::INIT_SPACE,
+        seeds = [WITHDRAWAL_EPOCH_SEED.as_bytes(), config.key().as_ref(), &config.load()?.withdrawal_epoch.to_le_bytes()],
+        bump
+    )]
+    pub withdrawal_epoch: AccountLoader<'info, WithdrawalEpoch>,
+
+    pub asset_token_program: Interface<'info, TokenInterface>,
+    pub token2022_program: Program<'info, Token2022>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+#[derive(Accounts)]
+pub struct ClaimRedeem<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+    /// CHECK: Blocked PDA of the user, must not be initialized
+    #[account(seeds = [BLOCKED_SEED.as_bytes(), user.key().as_ref()], bump)]
+    pub user_blocked: UncheckedAccount<'info>,
+
+    #[account(mint::token_program = asset_token_program)]
+    pub asset_mint: InterfaceAccount<'info, Mint>,
+    #[account(init_if_needed, payer = user, associated_token::mint = asset_mint, associated_token::authority = user, associated_token::token_program = asset_token_program)]
+    pub user_asset_vault: InterfaceAccount<'info, TokenAccount>,
+    #[account(mut, associated_token::mint = asset_mint, associated_token::authority = shares_mint, associated_token::token_program = asset_token_program)]
+    pub asset_vault: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(seeds = [SHARES_MINT_SEED.as_bytes()], bump, mint::token_program = token2022_program)]
+    pub shares_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(mut, seeds = [CONFIG_SEED.as_bytes()], bump, has_one = asset_mint)]
+    pub config: AccountLoader<'info, Config>,
+    #[account(
+        mut,
+        seeds = [USER_POSITION_SEED.as_bytes(), config.key().as_ref(), user.key().as_ref()],
+        bump
+    )]
+    pub user_position: AccountLoader<'info, UserPosition>,
+    #[account(
+        mut,
+        seeds = [REDEEM_REQUEST_SEED.as_bytes(), config.key().as_ref(), user.key().as_ref()],
+        bump
+    )]
+    pub redeem_request: AccountLoader<'info, RedeemRequest>,
+    #[account(
+        mut,
+        seeds = [WITHDRAWAL_EPOCH_SEED.as_bytes(), config.key().as_ref(), &redeem_request.load()?.epoch.to_le_bytes()],
+        bump
+    )]
+    pub withdrawal_epoch: Option<AccountLoader<'info, WithdrawalEpoch>>,
+
+    pub asset_token_program: Interface<'info, TokenInterface>,
+    pub token2022_program: Program<'info, Token2022>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct CancelRedeemRequest<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+    /// CHECK: Blocked PDA of the user, must not be initialized
+    #[account(seeds = [BLOCKED_SEED.as_bytes(), user.key().as_ref()], bump)]
+    pub user_blocked: UncheckedAccount<'info>,
+
+    #[account(seeds = [SHARES_MINT_SEED.as_bytes()], bump, mint::token_program = token2022_program)]
+    pub shares_mint: InterfaceAccount<'info, Mint>,
+    #[account(mut, associated_token::mint = shares_mint, associated_token::authority = user, associated_token::token_program = token2022_program)]
+    pub user_shares_vault: InterfaceAccount<'info, TokenAccount>,
+    #[account(mut, seeds = [REDEEM_ESCROW_SEED.as_bytes()], bump)]
+    pub redeem_escrow: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(mut, seeds = [CONFIG_SEED.as_bytes()], bump)]
+    pub config: AccountLoader<'info, Config>,
+    #[account(
+        mut,
+        seeds = [REDEEM_REQUEST_SEED.as_bytes(), config.key().as_ref(), user.key().as_ref()],
+        bump
+    )]
+    pub redeem_request: AccountLoader<'info, RedeemRequest>,
+
+    pub token2022_program: Program<'info, Token2022>,
+}
+
+pub fn process_request_redeem<'info>(
+    ctx: Context<'_, '_, 'info, 'info, RequestRedeem<'info>>,
+    shares: u64,
+) -> Result<()> {
+    let mut config = ctx.accounts.config.load_mut()?;
+    config.check_transferable_shares()?;
+    let clock = Clock::get()?;
+    let now = clock.unix_timestamp;
+    let user_position = UserPosition::load_or_init(
+        &ctx.accounts.user_position,
+        ctx.accounts.config.key(),
+        ctx.accounts.user.key(),
+    )?;
+    user_position.check_cooldown(config.withdraw_cooldown, now)?;
+    user_position.check_same_slot(clock.slot)?;
+    config.check_withdraw_report_slot(clock.slot)?;
+    let mut redeem_request = RedeemRequest::load_or_init(
+        &ctx.accounts.redeem_request,
+        ctx.accounts.config.key(),
+        ctx.accounts.user.key(),
+    )?;
+    redeem_request.request(shares, config.withdrawal_epoch, now)?;
+    ctx.accounts
+        .transfer_shares_to_escrow(ctx.remaining_accounts, shares)?;
+    config.pending_redeem_shares = config.pending_redeem_shares.saturating_add(shares);
+    Ok(())
+}
+
+pub fn process_close_withdrawal_epoch(ctx: Context<CloseWithdrawalEpoch>) -> Result<()> {
+    let mut config = ctx.accounts.config.load_mut()?;
+    let requested_shares = config.pending_redeem_shares;
+    if requested_shares == 0 {
+        return Err(ErrorCode::NoPendingRedeemRequest.into());
+    }
+    let now = Clock::get()?.unix_timestamp;
+    let total_assets = config.total_assets(ctx.accounts.asset_vault.amount);
+    let total_shares = config.total_supply(ctx.accounts.shares_mint.supply, now);
+    // Fill as many requested shares as the vault's idle liquidity and the withdrawal rate
+    // limit allow
+    let max_shares = preview_deposit_internal(
+        config
+            .idle_assets(ctx.accounts.asset_vault.amount)
+            .min(config.max_withdraw(total_assets, now)),
+        total_assets,
+        total_shares,
+        config.decimals_offset,
+    )?;
+    let filled_shares = requested_shares.min(max_shares);
+    let filled_assets = preview_redeem_internal(
+        filled_shares,
+        total_assets,
+        total_shares,
+        config.decimals_offset,
+    )?;
+    if filled_shares > 0 {
+        ctx.accounts
+            .burn_shares_from_escrow(ctx.bumps.shares_mint, filled_shares)?;
+    }
+    ctx.accounts.withdrawal_epoch.load_init()?.close(
+        ctx.accounts.config.key(),
+        config.withdrawal_epoch,
+        requested_shares,
+        filled_shares,
+        filled_assets,
+        now,
+    )?;
+    config.consume_withdraw_limit(total_assets, filled_assets, now)?;
+    config.claimable_assets = config.claimable_assets.saturating_add(filled_assets);
+    config.pending_redeem_shares = requested_shares - filled_shares;
+    config.withdrawal_epoch += 1;
+    Ok(())
+}
+
+/// Locks the exchange rate for all pending redeem requests by closing the current withdrawal
+/// epoch. Kept for keepers of the per-request API; requests are no longer filled one at a time.
+pub fn process_fulfill_redeem(ctx: Context<CloseWithdrawalEpoch>) -> Result<()> {
+    process_close_withdrawal_epoch(ctx)
+}
+
+pub fn process_claim_redeem(ctx: Context<ClaimRedeem>) -> Result<()> {
+    let is_empty = {
+        let mut config = ctx.accounts.config.load_mut()?;
+        let mut user_position = ctx.accounts.user_position.load_mut()?;
+        let mut redeem_request = ctx.accounts.redeem_request.load_mut()?;
+        // Claims advance one closed epoch at a time
+        let processed = match &ctx.accounts.withdrawal_epoch {
+            Some(withdrawal_epoch) => {
+                redeem_request.process_epoch(&mut *withdrawal_epoch.load_mut()?)?;
+                true
+            }
+            None => false,
+        };
+        if processed && !redeem_request.has_claimable() {
+            return Ok(());
+        }
+        let assets = redeem_request.claim()?;
+        ctx.accounts
+            .transfer_assets_from_vault_to_user(ctx.bumps.shares_mint, assets)?;
+        config.claimable_assets = config.claimable_assets.saturating_sub(assets);
+        user_position.record_withdrawal(assets)?;
+        redeem_request.is_empty()
+    };
+    if is_empty {
+        ctx.accounts
+            .redeem_request
+            .close(ctx.accounts.user.to_account_info())?;
+    }
+    Ok(())
+}
+
+pub fn process_cancel_redeem_request<'info>(
+    ctx: Context<'_, '_, 'info, 'info, CancelRedeemRequest<'info>>,
+) -> Result<()> {
+    let is_empty = {
+        let mut config = ctx.accounts.config.load_mut()?;
+        config.check_cancellations_unlocked()?;
+        let mut redeem_request = ctx.accounts.redeem_request.load_mut()?;
+        let shares = redeem_request.cancel(config.withdrawal_epoch)?;
+        ctx.accounts.transfer_shares_from_escrow_to_user(
+            ctx.bumps.shares_mint,
+            ctx.remaining_accounts,
+            shares,
+        )?;
+        config.pending_redeem_shares = config.pending_redeem_shares.saturating_sub(shares);
+        redeem_request.is_empty()
+    };
+    if is_empty {
+        ctx.accounts
+            .redeem_request
+            .close(ctx.accounts.user.to_account_info())?;
+    }
+    Ok(())
+}
+
+impl<'info> RequestRedeem<'info> {
+    pub fn transfer_shares_to_escrow(
+        &self,
+        remaining_accounts: &[AccountInfo<'info>],
+        amount: u64,
+    ) -> Result<()> {
+        transfer_shares_checked(
+            CpiContext::new(
+                self.token2022_program.to_account_info(),
+                TransferChecked {
+                    from: self.user_shares_vault.to_account_info(),
+                    to: self.redeem_escrow.to_account_info(),
+                    mint: self.shares_mint.to_account_info(),
+                    authority: self.user.to_account_info(),
+                },
+            )
+            .with_remaining_accounts(remaining_accounts.to_vec()),
+            amount,
+            self.shares_mint.decimals,
+        )?;
+        Ok(())
+    }
+}
+
+impl<'info> CancelRedeemRequest<'info> {
+    pub fn transfer_shares_from_escrow_to_user(
+        &self,
+        bump: u8,
+        remaining_accounts: &[AccountInfo<'info>],
+        amount: u64,
+    ) -> Result<()> {
+        let seeds = &[SHARES_MINT_SEED.as_bytes(), &[bump]];
+        let signer_seeds = &[&seeds[..]];
+        transfer_shares_checked(
+            CpiContext::new_with_signer(
+                self.token2022_program.to_account_info(),
+                TransferChecked {
+                    from: self.redeem_escrow.to_account_info(),
+                    to: self.user_shares_vault.to_account_info(),
+                    mint: self.shares_mint.to_account_info(),
+                    authority: self.shares_mint.to_account_info(),
+                },
+                signer_seeds,
+            )
+            .with_remaining_accounts(remaining_accounts.to_vec()),
+            amount,
+            self.shares_mint.decimals,
+        )?;
+        Ok(())
+    }
+}
+
+impl<'info> CloseWithdrawalEpoch<'info> {
+    pub fn burn_shares_from_escrow(&self, bump: u8, amount: u64) -> Result<()> {
+        let seeds = &[SHARES_MINT_SEED.as_bytes(), &[bump]];
+        let signer_seeds = &[&seeds[..]];
+        burn_checked(
+            CpiContext::new_with_signer(
+                self.token2022_program.to_account_info(),
+                BurnChecked {
+                    mint: self.shares_mint.to_account_info(),
+                    authority: self.shares_mint.to_account_info(),
+                    from: self.redeem_escrow.to_account_info(),
+                },
+                signer_seeds,
+            ),
+            amount,
+            self.shares_mint.decimals,
+        )?;
+        Ok(())
+    }
+}
+
+impl<'info> ClaimRedeem<'info> {
+    pub fn transfer_assets_from_vault_to_user(&self, bump: u8, amount: u64) -> Result<()> {
+        let seeds = &[SHARES_MINT_SEED.as_bytes(), &[bump]];
+        let signer_seeds = &[&seeds[..]];
+        transfer_checked(
+            CpiContext::new_with_signer(
+                self.asset_token_program.to_account_info(),
+                TransferChecked {
+                    from: self.asset_vault.to_account_info(),
+                    to: self.user_asset_vault.to_account_info(),
+                    mint: self.asset_mint.to_account_info(),
+                    authority: self.shares_mint.to_account_info(),
+                },
+                signer_seeds,
+            ),
+            amount,
+            self.asset_mint.decimals,
+        )?;
+        Ok(())
+    }
+}
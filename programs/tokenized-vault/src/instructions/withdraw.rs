@@ -1,25 +1,32 @@
 use anchor_lang::prelude::*;
 
-use anchor_spl::associated_token::AssociatedToken;
 use anchor_spl::token_interface::{
     burn_checked, transfer_checked, BurnChecked, Mint, Token2022, TokenAccount, TokenInterface,
     TransferChecked,
 };
 
-use crate::states::Config;
+use crate::states::{Config, UserPosition};
 use crate::utils::{
-    preview_redeem_internal, preview_withdraw_internal, CONFIG_SEED, SHARES_MINT_SEED,
+    amount_before_fee, preview_redeem_internal, preview_withdraw_internal, BLOCKED_SEED,
+    CONFIG_SEED, SHARES_MINT_SEED, USER_POSITION_SEED,
 };
 
 #[derive(Accounts)]
 pub struct Withdraw<'info> {
     #[account(mut)]
     pub user: Signer<'info>,
+    /// CHECK: Blocked PDA of the user, must not be initialized
+    #[account(seeds = [BLOCKED_SEED.as_bytes(), user.key().as_ref()], bump)]
+    pub user_blocked: UncheckedAccount<'info>,
 
     #[account(mut, mint::token_program = asset_token_program)]
     pub asset_mint: InterfaceAccount<'info, Mint>,
-    #[account(init_if_needed, payer = user, associated_token::mint = asset_mint, associated_token::authority = user, associated_token::token_program = asset_token_program)]
+    /// Receiver of the assets, any token account of the asset mint
+    #[account(mut, token::mint = asset_mint, token::token_program = asset_token_program)]
     pub user_asset_vault: InterfaceAccount<'info, TokenAccount>,
+    /// CHECK: Blocked PDA of the receiver's owner, must not be initialized
+    #[account(seeds = [BLOCKED_SEED.as_bytes(), user_asset_vault.owner.as_ref()], bump)]
+    pub receiver_blocked: UncheckedAccount<'info>,
 
     #[account(mut, associated_token::mint = asset_mint, associated_token::authority = shares_mint, associated_token::token_program = asset_token_program)]
     pub asset_vault: InterfaceAccount<'info, TokenAccount>,
@@ -29,39 +36,78 @@ pub struct Withdraw<'info> {
     #[account(mut, associated_token::mint = shares_mint, associated_token::authority = user, associated_token::token_program = token2022_program)]
     pub user_shares_vault: InterfaceAccount<'info, TokenAccount>,
 
-    #[account(seeds = [CONFIG_SEED.as_bytes()], bump, has_one = asset_mint)]
+    #[account(mut, seeds = [CONFIG_SEED.as_bytes()], bump, has_one = asset_mint)]
     pub config: AccountLoader<'info, Config>,
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = 8 + UserPosition::INIT_SPACE,
+        seeds = [USER_POSITION_SEED.as_bytes(), config.key().as_ref(), user.key().as_ref()],
+        bump
+    )]
+    pub user_position: AccountLoader<'info, UserPosition>,
 
     pub asset_token_program: Interface<'info, TokenInterface>,
     pub token2022_program: Program<'info, Token2022>,
-    pub associated_token_program: Program<'info, AssociatedToken>,
     pub system_program: Program<'info, System>,
     pub rent: Sysvar<'info, Rent>,
 }
 
 pub fn process_withdraw(ctx: Context<Withdraw>, assets: u64) -> Result<()> {
+    let mut config = ctx.accounts.config.load_mut()?;
+    let mut user_position = UserPosition::load_or_init(
+        &ctx.accounts.user_position,
+        ctx.accounts.config.key(),
+        ctx.accounts.user.key(),
+    )?;
+    let clock = Clock::get()?;
+    let now = clock.unix_timestamp;
+    user_position.check_cooldown(config.withdraw_cooldown, now)?;
+    user_position.check_same_slot(clock.slot)?;
+    config.check_withdraw_report_slot(clock.slot)?;
+    let total_assets = config.total_assets(ctx.accounts.asset_vault.amount);
+    // Gross up so the user receives `assets` net of transfer fees
+    let amount = amount_before_fee(&ctx.accounts.asset_mint.to_account_info(), assets)?;
+    config.check_idle_assets(ctx.accounts.asset_vault.amount, amount)?;
     let shares = preview_withdraw_internal(
-        assets,
-        ctx.accounts.asset_vault.amount,
-        ctx.accounts.shares_mint.supply,
-        ctx.accounts.config.load()?.decimals_offset,
+        amount,
+        total_assets,
+        config.total_supply(ctx.accounts.shares_mint.supply, now),
+        config.decimals_offset,
     )?;
+    config.consume_withdraw_limit(total_assets, amount, now)?;
     ctx.accounts
-        .transfer_assets_from_vault_to_user(ctx.bumps.shares_mint, assets)?;
+        .transfer_assets_from_vault_to_user(ctx.bumps.shares_mint, amount)?;
     ctx.accounts.burn_shares_from_user(shares)?;
+    user_position.record_withdrawal(amount)?;
     Ok(())
 }
 
 pub fn process_redeem(ctx: Context<Withdraw>, shares: u64) -> Result<()> {
+    let mut config = ctx.accounts.config.load_mut()?;
+    let mut user_position = UserPosition::load_or_init(
+        &ctx.accounts.user_position,
+        ctx.accounts.config.key(),
+        ctx.accounts.user.key(),
+    )?;
+    let clock = Clock::get()?;
+    let now = clock.unix_timestamp;
+    user_position.check_cooldown(config.withdraw_cooldown, now)?;
+    user_position.check_same_slot(clock.slot)?;
+    config.check_withdraw_report_slot(clock.slot)?;
+    let total_assets = config.total_assets(ctx.accounts.asset_vault.amount);
     let assets = preview_redeem_internal(
         shares,
-        ctx.accounts.asset_vault.amount,
-        ctx.accounts.shares_mint.supply,
-        ctx.accounts.config.load()?.decimals_offset,
+        total_assets,
+        config.total_supply(ctx.accounts.shares_mint.supply, now),
+        config.decimals_offset,
     )?;
+    config.check_idle_assets(ctx.accounts.asset_vault.amount, assets)?;
+    config.consume_withdraw_limit(total_assets, assets, now)?;
     ctx.accounts
         .transfer_assets_from_vault_to_user(ctx.bumps.shares_mint, assets)?;
     ctx.accounts.burn_shares_from_user(shares)?;
+    user_position.record_withdrawal(assets)?;
     Ok(())
 }
 
@@ -2,6 +2,8 @@ use anchor_lang::prelude::*;
 use core as core_;
 
 mod access_controls;
+#[cfg(feature = "client")]
+pub mod client;
 mod error;
 mod instructions;
 mod states;
@@ -10,6 +12,7 @@ mod views;
 
 use access_controls::*;
 use instructions::*;
+use utils::constants::*;
 use views::*;
 
 declare_id!("6DG8Q5KBjC8UipDajgikmDR6pM8nAtPLzctFpgCUDXbM");
@@ -18,30 +21,103 @@ declare_id!("6DG8Q5KBjC8UipDajgikmDR6pM8nAtPLzctFpgCUDXbM");
 pub mod tokenized_vault {
     use super::*;
 
-    pub fn initialize(ctx: Context<Initialize>, metadata: TokenMetadataArgs) -> Result<()> {
-        process_initialize(ctx, metadata)
+    pub fn initialize(
+        ctx: Context<Initialize>,
+        metadata: TokenMetadataArgs,
+        options: u16,
+    ) -> Result<()> {
+        process_initialize(ctx, metadata, options)
     }
 
     /// =====================================================================================
     /// User Instructions
     /// =====================================================================================
 
+    #[access_control(
+        only_allowlisted(&ctx.accounts.config, &ctx.accounts.user_access)
+        not_blocked(&ctx.accounts.user_blocked)
+    )]
     pub fn deposit(ctx: Context<Deposit>, assets: u64) -> Result<()> {
         process_deposit(ctx, assets)
     }
 
+    #[access_control(
+        only_allowlisted(&ctx.accounts.config, &ctx.accounts.user_access)
+        not_blocked(&ctx.accounts.user_blocked)
+    )]
     pub fn mint(ctx: Context<Deposit>, shares: u64) -> Result<()> {
         process_mint(ctx, shares)
     }
 
+    #[access_control(
+        only_allowlisted(&ctx.accounts.config, &ctx.accounts.user_access)
+        not_blocked(&ctx.accounts.user_blocked)
+    )]
+    pub fn request_deposit(ctx: Context<RequestDeposit>, assets: u64) -> Result<()> {
+        process_request_deposit(ctx, assets)
+    }
+
+    #[access_control(not_blocked(&ctx.accounts.user_blocked))]
+    pub fn cancel_deposit_request(ctx: Context<CancelDepositRequest>) -> Result<()> {
+        process_cancel_deposit_request(ctx)
+    }
+
+    #[access_control(not_blocked(&ctx.accounts.user_blocked))]
+    pub fn claim_deposit<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ClaimDeposit<'info>>,
+    ) -> Result<()> {
+        process_claim_deposit(ctx)
+    }
+
+    #[access_control(
+        not_blocked(&ctx.accounts.user_blocked)
+        not_blocked(&ctx.accounts.receiver_blocked)
+    )]
     pub fn withdraw(ctx: Context<Withdraw>, assets: u64) -> Result<()> {
         process_withdraw(ctx, assets)
     }
 
+    #[access_control(
+        not_blocked(&ctx.accounts.user_blocked)
+        not_blocked(&ctx.accounts.receiver_blocked)
+    )]
     pub fn redeem(ctx: Context<Withdraw>, shares: u64) -> Result<()> {
         process_redeem(ctx, shares)
     }
 
+    #[access_control(
+        only_allowlisted(&ctx.accounts.config, &ctx.accounts.user_access)
+        not_blocked(&ctx.accounts.user_blocked)
+    )]
+    pub fn deposit_sol(ctx: Context<DepositSol>, lamports: u64) -> Result<()> {
+        process_deposit_sol(ctx, lamports)
+    }
+
+    #[access_control(not_blocked(&ctx.accounts.user_blocked))]
+    pub fn withdraw_sol(ctx: Context<WithdrawSol>, shares: u64) -> Result<()> {
+        process_withdraw_sol(ctx, shares)
+    }
+
+    #[access_control(not_blocked(&ctx.accounts.user_blocked))]
+    pub fn request_redeem<'info>(
+        ctx: Context<'_, '_, 'info, 'info, RequestRedeem<'info>>,
+        shares: u64,
+    ) -> Result<()> {
+        process_request_redeem(ctx, shares)
+    }
+
+    #[access_control(not_blocked(&ctx.accounts.user_blocked))]
+    pub fn cancel_redeem_request<'info>(
+        ctx: Context<'_, '_, 'info, 'info, CancelRedeemRequest<'info>>,
+    ) -> Result<()> {
+        process_cancel_redeem_request(ctx)
+    }
+
+    #[access_control(not_blocked(&ctx.accounts.user_blocked))]
+    pub fn claim_redeem(ctx: Context<ClaimRedeem>) -> Result<()> {
+        process_claim_redeem(ctx)
+    }
+
     /// =====================================================================================
     /// Views
     /// =====================================================================================
@@ -62,6 +138,50 @@ pub mod tokenized_vault {
         process_preview_redeem(ctx, shares)
     }
 
+    pub fn total_supply(ctx: Context<Preview>) -> Result<u64> {
+        process_total_supply(ctx)
+    }
+
+    pub fn locked_shares(ctx: Context<Preview>) -> Result<u64> {
+        process_locked_shares(ctx)
+    }
+
+    pub fn max_deposit(ctx: Context<MaxPreview>) -> Result<u64> {
+        process_max_deposit(ctx)
+    }
+
+    pub fn max_mint(ctx: Context<MaxPreview>) -> Result<u64> {
+        process_max_mint(ctx)
+    }
+
+    pub fn max_withdraw(ctx: Context<MaxPreview>) -> Result<u64> {
+        process_max_withdraw(ctx)
+    }
+
+    pub fn max_redeem(ctx: Context<MaxPreview>) -> Result<u64> {
+        process_max_redeem(ctx)
+    }
+
+    pub fn pending_deposit_request(ctx: Context<DepositRequestView>) -> Result<u64> {
+        process_pending_deposit_request(ctx)
+    }
+
+    pub fn claimable_deposit_request(ctx: Context<DepositRequestView>) -> Result<u64> {
+        process_claimable_deposit_request(ctx)
+    }
+
+    pub fn pending_redeem_request(ctx: Context<RedeemRequestView>) -> Result<u64> {
+        process_pending_redeem_request(ctx)
+    }
+
+    pub fn claimable_redeem_request(ctx: Context<RedeemRequestView>) -> Result<u64> {
+        process_claimable_redeem_request(ctx)
+    }
+
+    pub fn claimable_redeem_assets(ctx: Context<RedeemRequestView>) -> Result<u64> {
+        process_claimable_redeem_assets(ctx)
+    }
+
     /// =====================================================================================
     /// Admin Manager
     /// =====================================================================================
@@ -71,6 +191,14 @@ pub mod tokenized_vault {
         process_grant_permission(ctx, permission)
     }
 
+    #[access_control(only_permission_admin(&ctx.accounts.admin_access, permission))]
+    pub fn grant_permission_batch<'info>(
+        ctx: Context<'_, '_, 'info, 'info, UpdatePermissionBatch<'info>>,
+        permission: u128,
+    ) -> Result<()> {
+        process_grant_permission_batch(ctx, permission)
+    }
+
     #[access_control(only_permission_admin(&ctx.accounts.admin_access, permission))]
     pub fn revoke_permission(ctx: Context<UpdatePermission>, permission: u128) -> Result<()> {
         process_revoke_permission(ctx, permission)
@@ -91,4 +219,168 @@ pub mod tokenized_vault {
     ) -> Result<()> {
         process_revoke_permission_admin(ctx, permission)
     }
+
+    #[access_control(only_owner(&ctx.accounts.config, *ctx.accounts.owner.key))]
+    pub fn set_allowlist_enabled(ctx: Context<UpdateConfig>, enabled: bool) -> Result<()> {
+        process_set_allowlist_enabled(ctx, enabled)
+    }
+
+    #[access_control(only_owner(&ctx.accounts.config, *ctx.accounts.owner.key))]
+    pub fn set_async_deposits(ctx: Context<UpdateConfig>, enabled: bool) -> Result<()> {
+        process_set_async_deposits(ctx, enabled)
+    }
+
+    #[access_control(only_owner(&ctx.accounts.config, *ctx.accounts.owner.key))]
+    pub fn set_report_slot_guard(ctx: Context<UpdateConfig>, enabled: bool) -> Result<()> {
+        process_set_report_slot_guard(ctx, enabled)
+    }
+
+    #[access_control(only_owner(&ctx.accounts.config, *ctx.accounts.owner.key))]
+    pub fn set_performance_fee(
+        ctx: Context<UpdateFees>,
+        fee_bps: u64,
+        fee_recipient: Pubkey,
+    ) -> Result<()> {
+        process_set_performance_fee(ctx, fee_bps, fee_recipient)
+    }
+
+    #[access_control(only_owner(&ctx.accounts.config, *ctx.accounts.owner.key))]
+    pub fn set_profit_max_unlock_time(
+        ctx: Context<UpdateConfig>,
+        profit_max_unlock_time: u64,
+    ) -> Result<()> {
+        process_set_profit_max_unlock_time(ctx, profit_max_unlock_time)
+    }
+
+    #[access_control(only_permission(&ctx.accounts.access, COMPLIANCE_PERMISSION))]
+    pub fn block_address(ctx: Context<BlockAddress>) -> Result<()> {
+        process_block_address(ctx)
+    }
+
+    #[access_control(only_permission(&ctx.accounts.access, COMPLIANCE_PERMISSION))]
+    pub fn unblock_address(ctx: Context<UnblockAddress>) -> Result<()> {
+        process_unblock_address(ctx)
+    }
+
+    /// =====================================================================================
+    /// Limits
+    /// =====================================================================================
+
+    #[access_control(only_permission(&ctx.accounts.access, LIMIT_MANAGER_PERMISSION))]
+    pub fn set_deposit_cap(ctx: Context<UpdateLimits>, deposit_cap: u64) -> Result<()> {
+        process_set_deposit_cap(ctx, deposit_cap)
+    }
+
+    #[access_control(only_permission(&ctx.accounts.access, LIMIT_MANAGER_PERMISSION))]
+    pub fn set_default_deposit_limit(
+        ctx: Context<UpdateLimits>,
+        user_deposit_limit: u64,
+    ) -> Result<()> {
+        process_set_default_deposit_limit(ctx, user_deposit_limit)
+    }
+
+    #[access_control(only_permission(&ctx.accounts.access, LIMIT_MANAGER_PERMISSION))]
+    pub fn set_withdraw_cooldown(
+        ctx: Context<UpdateLimits>,
+        withdraw_cooldown: u64,
+    ) -> Result<()> {
+        process_set_withdraw_cooldown(ctx, withdraw_cooldown)
+    }
+
+    #[access_control(only_permission(&ctx.accounts.access, GUARDIAN_PERMISSION))]
+    pub fn set_withdraw_rate_limit(
+        ctx: Context<UpdateLimits>,
+        bps: u64,
+        window: u64,
+    ) -> Result<()> {
+        process_set_withdraw_rate_limit(ctx, bps, window)
+    }
+
+    #[access_control(only_permission(&ctx.accounts.access, GUARDIAN_PERMISSION))]
+    pub fn reset_withdraw_rate_limit(ctx: Context<UpdateLimits>) -> Result<()> {
+        process_reset_withdraw_rate_limit(ctx)
+    }
+
+    #[access_control(only_permission(&ctx.accounts.access, LIMIT_MANAGER_PERMISSION))]
+    pub fn set_user_deposit_limit(
+        ctx: Context<UpdateUserLimits>,
+        deposit_limit: Option<u64>,
+    ) -> Result<()> {
+        process_set_user_deposit_limit(ctx, deposit_limit)
+    }
+
+    #[access_control(only_permission(&ctx.accounts.access, KEEPER_PERMISSION))]
+    pub fn close_withdrawal_epoch(ctx: Context<CloseWithdrawalEpoch>) -> Result<()> {
+        process_close_withdrawal_epoch(ctx)
+    }
+
+    #[access_control(only_permission(&ctx.accounts.access, KEEPER_PERMISSION))]
+    pub fn fulfill_redeem(ctx: Context<CloseWithdrawalEpoch>) -> Result<()> {
+        process_fulfill_redeem(ctx)
+    }
+
+    #[access_control(only_permission(&ctx.accounts.access, KEEPER_PERMISSION))]
+    pub fn set_cancellations_locked(ctx: Context<UpdateSettlement>, locked: bool) -> Result<()> {
+        process_set_cancellations_locked(ctx, locked)
+    }
+
+    #[access_control(only_permission(&ctx.accounts.access, KEEPER_PERMISSION))]
+    pub fn settle_deposits(ctx: Context<SettleDeposits>) -> Result<()> {
+        process_settle_deposits(ctx)
+    }
+
+    #[access_control(only_permission(&ctx.accounts.access, METADATA_MANAGER_PERMISSION))]
+    pub fn update_shares_metadata(
+        ctx: Context<UpdateMetadata>,
+        metadata: TokenMetadataArgs,
+    ) -> Result<()> {
+        process_update_shares_metadata(ctx, metadata)
+    }
+
+    #[access_control(only_permission(&ctx.accounts.access, METADATA_MANAGER_PERMISSION))]
+    pub fn set_metadata_field(
+        ctx: Context<UpdateMetadata>,
+        key: String,
+        value: String,
+    ) -> Result<()> {
+        process_set_metadata_field(ctx, key, value)
+    }
+
+    #[access_control(only_permission(&ctx.accounts.access, METADATA_MANAGER_PERMISSION))]
+    pub fn sync_vault_metadata(ctx: Context<SyncMetadata>) -> Result<()> {
+        process_sync_vault_metadata(ctx)
+    }
+
+    #[access_control(only_permission(&ctx.accounts.access, KEEPER_PERMISSION))]
+    pub fn update_share_multiplier(ctx: Context<UpdateShareMultiplier>) -> Result<()> {
+        process_update_share_multiplier(ctx)
+    }
+
+    #[access_control(only_permission(&ctx.accounts.access, STRATEGY_MANAGER_PERMISSION))]
+    pub fn add_strategy(ctx: Context<AddStrategy>, max_debt: u64) -> Result<()> {
+        process_add_strategy(ctx, max_debt)
+    }
+
+    #[access_control(only_permission(&ctx.accounts.access, STRATEGY_MANAGER_PERMISSION))]
+    pub fn remove_strategy(ctx: Context<UpdateStrategy>) -> Result<()> {
+        process_remove_strategy(ctx)
+    }
+
+    #[access_control(only_permission(&ctx.accounts.access, STRATEGY_MANAGER_PERMISSION))]
+    pub fn update_max_debt(ctx: Context<UpdateStrategy>, max_debt: u64) -> Result<()> {
+        process_update_max_debt(ctx, max_debt)
+    }
+
+    #[access_control(only_permission(&ctx.accounts.access, DEBT_MANAGER_PERMISSION))]
+    pub fn update_debt<'info>(
+        ctx: Context<'_, '_, 'info, 'info, UpdateDebt<'info>>,
+        target_debt: u64,
+    ) -> Result<()> {
+        process_update_debt(ctx, target_debt)
+    }
+
+    #[access_control(only_permission(&ctx.accounts.access, KEEPER_PERMISSION))]
+    pub fn report<'info>(ctx: Context<'_, '_, 'info, 'info, Report<'info>>) -> Result<()> {
+        process_report(ctx)
+    }
 }
@@ -0,0 +1,36 @@
+use anchor_lang::prelude::*;
+
+#[account(zero_copy)]
+#[derive(InitSpace)]
+pub struct Blocked {
+    pub account: Pubkey,
+    pub blocked_at: i64,
+    pub _padding1: [u8; 8],
+    pub _padding2: [u64; 6],
+}
+
+impl Blocked {
+    pub fn initialize(&mut self, account: Pubkey, blocked_at: i64) -> Result<()> {
+        self.account = account;
+        self.blocked_at = blocked_at;
+        emit!(AddressBlocked { account });
+        Ok(())
+    }
+
+    pub fn release(&self) -> Result<()> {
+        emit!(AddressUnblocked {
+            account: self.account,
+        });
+        Ok(())
+    }
+}
+
+#[event]
+pub struct AddressBlocked {
+    pub account: Pubkey,
+}
+
+#[event]
+pub struct AddressUnblocked {
+    pub account: Pubkey,
+}
@@ -1,16 +1,440 @@
 use anchor_lang::prelude::*;
 
+use crate::error::ErrorCode;
+use crate::utils::{BPS_DENOMINATOR, MAX_PROFIT_UNLOCK_TIME, VERSION};
+
 #[account(zero_copy)]
 #[derive(InitSpace)]
 pub struct Config {
     pub owner: Pubkey,
     pub asset_mint: Pubkey,
     pub decimals_offset: u8,
-    pub _padding1: [u8; 7],
-    pub _padding2: [u64; 30],
+    pub allowlist_enabled: u8,
+    pub async_deposits: u8,
+    pub cancellations_locked: u8,
+    pub report_slot_guard: u8,
+    pub non_transferable_shares: u8,
+    pub shares_transfer_hook: u8,
+    /// Set once `deposit_cap` and `user_deposit_limit` hold configured values. Configs created
+    /// before the limits existed read them as zero from the padding.
+    pub limits_initialized: u8,
+    pub deposit_cap: u64,
+    pub user_deposit_limit: u64,
+    pub withdraw_cooldown: u64,
+    pub claimable_assets: u64,
+    pub deposit_epoch: u64,
+    pub pending_deposit_assets: u64,
+    pub withdrawal_epoch: u64,
+    pub pending_redeem_shares: u64,
+    pub withdraw_limit_bps: u64,
+    pub withdraw_limit_window: u64,
+    pub withdraw_bucket: u64,
+    pub withdraw_bucket_updated_at: i64,
+    pub last_report_slot: u64,
+    pub total_debt: u64,
+    pub performance_fee_bps: u64,
+    pub fee_recipient: Pubkey,
+    pub profit_max_unlock_time: u64,
+    /// Profit shares held by the vault that had not unlocked at `last_profit_update`
+    pub locked_shares: u64,
+    pub last_profit_update: i64,
+    pub full_profit_unlock_date: i64,
+    pub _padding2: [u64; 7],
+}
+
+impl Config {
+    pub fn is_allowlist_enabled(&self) -> bool {
+        self.allowlist_enabled != 0
+    }
+
+    pub fn set_allowlist_enabled(&mut self, enabled: bool) -> Result<()> {
+        self.allowlist_enabled = enabled as u8;
+        emit!(AllowlistUpdated { enabled });
+        Ok(())
+    }
+
+    pub fn is_async_deposits(&self) -> bool {
+        self.async_deposits != 0
+    }
+
+    pub fn set_async_deposits(&mut self, enabled: bool) -> Result<()> {
+        self.async_deposits = enabled as u8;
+        emit!(DepositModeUpdated {
+            async_deposits: enabled,
+        });
+        Ok(())
+    }
+
+    pub fn check_sync_deposits(&self) -> Result<()> {
+        if self.is_async_deposits() {
+            return Err(ErrorCode::SyncDepositsDisabled.into());
+        }
+        Ok(())
+    }
+
+    pub fn check_async_deposits(&self) -> Result<()> {
+        if !self.is_async_deposits() {
+            return Err(ErrorCode::AsyncDepositsDisabled.into());
+        }
+        Ok(())
+    }
+
+    /// Soulbound shares cannot move through the request escrows.
+    pub fn check_transferable_shares(&self) -> Result<()> {
+        if self.non_transferable_shares != 0 {
+            return Err(ErrorCode::NonTransferableShares.into());
+        }
+        Ok(())
+    }
+
+    pub fn set_cancellations_locked(&mut self, locked: bool) -> Result<()> {
+        self.cancellations_locked = locked as u8;
+        emit!(CancellationsLockUpdated { locked });
+        Ok(())
+    }
+
+    pub fn check_cancellations_unlocked(&self) -> Result<()> {
+        if self.cancellations_locked != 0 {
+            return Err(ErrorCode::CancellationsLocked.into());
+        }
+        Ok(())
+    }
+
+    /// Keys of the vault parameters in the shares metadata, reserved to the vault.
+    pub const METADATA_KEYS: [&'static str; 4] = [
+        "asset_mint",
+        "decimals_offset",
+        "performance_fee_bps",
+        "version",
+    ];
+
+    /// Vault parameters published as additional fields of the shares metadata.
+    pub fn metadata_fields(&self) -> Vec<(&'static str, String)> {
+        let values = [
+            self.asset_mint.to_string(),
+            self.decimals_offset.to_string(),
+            self.performance_fee_bps.to_string(),
+            VERSION.to_string(),
+        ];
+        Self::METADATA_KEYS.into_iter().zip(values).collect()
+    }
+
+    pub fn check_unreserved_metadata_key(key: &str) -> Result<()> {
+        if Self::METADATA_KEYS.contains(&key) {
+            return Err(ErrorCode::ReservedMetadataField.into());
+        }
+        Ok(())
+    }
+
+    /// Assets in the vault balance that are neither reserved for fulfilled redeem requests
+    /// nor held for deposit requests awaiting settlement.
+    pub fn idle_assets(&self, vault_balance: u64) -> u64 {
+        vault_balance
+            .saturating_sub(self.claimable_assets)
+            .saturating_sub(self.pending_deposit_assets)
+    }
+
+    /// Assets backing outstanding shares: the idle assets plus the debt of all strategies.
+    pub fn total_assets(&self, vault_balance: u64) -> u64 {
+        self.idle_assets(vault_balance)
+            .saturating_add(self.total_debt)
+    }
+
+    /// Withdrawals are paid from idle assets only; the debt stays with the strategies.
+    pub fn check_idle_assets(&self, vault_balance: u64, assets: u64) -> Result<()> {
+        if assets > self.idle_assets(vault_balance) {
+            return Err(ErrorCode::InsufficientIdleAssets.into());
+        }
+        Ok(())
+    }
+
+    pub fn set_performance_fee(&mut self, fee_bps: u64, fee_recipient: Pubkey) -> Result<()> {
+        if fee_bps > BPS_DENOMINATOR {
+            return Err(ErrorCode::InvalidPerformanceFee.into());
+        }
+        self.performance_fee_bps = fee_bps;
+        self.fee_recipient = fee_recipient;
+        emit!(PerformanceFeeUpdated {
+            fee_bps,
+            fee_recipient,
+        });
+        Ok(())
+    }
+
+    /// Assets taken as performance fee out of a reported `gain`.
+    pub fn performance_fee(&self, gain: u64) -> u64 {
+        (gain as u128 * self.performance_fee_bps as u128 / BPS_DENOMINATOR as u128) as u64
+    }
+
+    pub fn set_profit_max_unlock_time(&mut self, profit_max_unlock_time: u64) -> Result<()> {
+        if profit_max_unlock_time > MAX_PROFIT_UNLOCK_TIME {
+            return Err(ErrorCode::InvalidProfitUnlockTime.into());
+        }
+        self.profit_max_unlock_time = profit_max_unlock_time;
+        emit!(ProfitMaxUnlockTimeUpdated {
+            profit_max_unlock_time,
+        });
+        Ok(())
+    }
+
+    /// Locked profit shares released linearly since `last_profit_update`. They stay in the
+    /// mint supply until the next report burns them.
+    pub fn unlocked_shares(&self, now: i64) -> u64 {
+        if now >= self.full_profit_unlock_date {
+            return self.locked_shares;
+        }
+        let elapsed = now.saturating_sub(self.last_profit_update).max(0) as u128;
+        let period = (self.full_profit_unlock_date - self.last_profit_update) as u128;
+        (self.locked_shares as u128 * elapsed / period) as u64
+    }
+
+    /// Shares pricing the vault: the mint supply without the unlocked profit shares.
+    pub fn total_supply(&self, supply: u64, now: i64) -> u64 {
+        supply.saturating_sub(self.unlocked_shares(now))
+    }
+
+    /// Stops tracking the shares unlocked so far and returns them, to be burned.
+    pub fn release_unlocked_shares(&mut self, now: i64) -> u64 {
+        let unlocked = self.unlocked_shares(now);
+        self.locked_shares -= unlocked;
+        self.last_profit_update = now;
+        unlocked
+    }
+
+    /// Adds newly minted profit shares to the lock. The unlock period is the average of the
+    /// remaining period of the shares already locked and `profit_max_unlock_time`, weighted
+    /// by their amounts. Call after `release_unlocked_shares`.
+    pub fn lock_shares(&mut self, shares: u64, now: i64) {
+        let total = self.locked_shares as u128 + shares as u128;
+        if total == 0 {
+            self.full_profit_unlock_date = now;
+            return;
+        }
+        let remaining_time = self.full_profit_unlock_date.saturating_sub(now).max(0) as u128;
+        let period = (self.locked_shares as u128 * remaining_time
+            + shares as u128 * self.profit_max_unlock_time as u128)
+            / total;
+        self.locked_shares = total as u64;
+        self.last_profit_update = now;
+        self.full_profit_unlock_date = now + period as i64;
+    }
+
+    pub fn set_report_slot_guard(&mut self, enabled: bool) -> Result<()> {
+        self.report_slot_guard = enabled as u8;
+        emit!(ReportSlotGuardUpdated { enabled });
+        Ok(())
+    }
+
+    /// Rejects deposits landing in the same slot as the latest yield report.
+    pub fn check_report_slot(&self, slot: u64) -> Result<()> {
+        if self.is_report_slot(slot) {
+            return Err(ErrorCode::DepositInReportSlot.into());
+        }
+        Ok(())
+    }
+
+    /// Rejects withdrawals after the latest yield report in its slot, whoever holds the shares,
+    /// so deposits made earlier in the slot cannot leave through another wallet.
+    pub fn check_withdraw_report_slot(&self, slot: u64) -> Result<()> {
+        if self.is_report_slot(slot) {
+            return Err(ErrorCode::WithdrawalInReportSlot.into());
+        }
+        Ok(())
+    }
+
+    pub fn is_report_slot(&self, slot: u64) -> bool {
+        self.report_slot_guard != 0 && self.last_report_slot == slot
+    }
+
+    /// Gives configs created before the limits existed the defaults of new vaults, so that
+    /// a zero cap or limit always means what was set.
+    pub fn init_limits(&mut self) {
+        if self.limits_initialized == 0 {
+            self.deposit_cap = u64::MAX;
+            self.user_deposit_limit = u64::MAX;
+            self.limits_initialized = 1;
+        }
+    }
+
+    /// Assets that can still be deposited under the cap. Uncapped until the limits are
+    /// initialized; a cap of zero pauses deposits.
+    pub fn max_deposit(&self, total_assets: u64) -> u64 {
+        if self.limits_initialized == 0 {
+            return u64::MAX;
+        }
+        self.deposit_cap.saturating_sub(total_assets)
+    }
+
+    pub fn check_deposit_cap(&self, total_assets: u64, assets: u64) -> Result<()> {
+        if assets > self.max_deposit(total_assets) {
+            return Err(ErrorCode::DepositCapExceeded.into());
+        }
+        Ok(())
+    }
+
+    pub fn set_deposit_cap(&mut self, deposit_cap: u64) -> Result<()> {
+        self.init_limits();
+        self.deposit_cap = deposit_cap;
+        emit!(DepositCapUpdated { deposit_cap });
+        Ok(())
+    }
+
+    /// Deposit limit of users without an override. Unlimited until the limits are initialized;
+    /// a limit of zero blocks their deposits.
+    pub fn default_deposit_limit(&self) -> u64 {
+        if self.limits_initialized == 0 {
+            return u64::MAX;
+        }
+        self.user_deposit_limit
+    }
+
+    pub fn set_user_deposit_limit(&mut self, user_deposit_limit: u64) -> Result<()> {
+        self.init_limits();
+        self.user_deposit_limit = user_deposit_limit;
+        emit!(UserDepositLimitDefaultUpdated { user_deposit_limit });
+        Ok(())
+    }
+
+    pub fn set_withdraw_rate_limit(&mut self, bps: u64, window: u64) -> Result<()> {
+        if bps > BPS_DENOMINATOR || (bps > 0 && window == 0) {
+            return Err(ErrorCode::InvalidWithdrawRateLimit.into());
+        }
+        self.withdraw_limit_bps = bps;
+        self.withdraw_limit_window = window;
+        emit!(WithdrawRateLimitUpdated { bps, window });
+        Ok(())
+    }
+
+    pub fn reset_withdraw_rate_limit(&mut self) -> Result<()> {
+        self.withdraw_bucket = 0;
+        emit!(WithdrawRateLimitReset {});
+        Ok(())
+    }
+
+    /// Assets withdrawn within the current window; the bucket drains linearly over the window.
+    pub fn withdraw_bucket(&self, now: i64) -> u64 {
+        let elapsed = now.saturating_sub(self.withdraw_bucket_updated_at).max(0) as u64;
+        if elapsed >= self.withdraw_limit_window {
+            return 0;
+        }
+        (self.withdraw_bucket as u128 * (self.withdraw_limit_window - elapsed) as u128
+            / self.withdraw_limit_window as u128) as u64
+    }
+
+    /// Assets that can still leave the vault in the current window. A zero
+    /// `withdraw_limit_bps` disables the limiter.
+    pub fn max_withdraw(&self, total_assets: u64, now: i64) -> u64 {
+        if self.withdraw_limit_bps == 0 {
+            return u64::MAX;
+        }
+        let limit = (total_assets as u128 * self.withdraw_limit_bps as u128
+            / BPS_DENOMINATOR as u128) as u64;
+        limit.saturating_sub(self.withdraw_bucket(now))
+    }
+
+    pub fn consume_withdraw_limit(
+        &mut self,
+        total_assets: u64,
+        assets: u64,
+        now: i64,
+    ) -> Result<()> {
+        if self.withdraw_limit_bps == 0 {
+            return Ok(());
+        }
+        if assets > self.max_withdraw(total_assets, now) {
+            return Err(ErrorCode::WithdrawalRateLimited.into());
+        }
+        self.withdraw_bucket = self.withdraw_bucket(now).saturating_add(assets);
+        self.withdraw_bucket_updated_at = now;
+        Ok(())
+    }
+
+    /// The cooldown is recorded per depositor, so it only holds if shares cannot change hands.
+    pub fn set_withdraw_cooldown(&mut self, withdraw_cooldown: u64) -> Result<()> {
+        if withdraw_cooldown > 0 && self.non_transferable_shares == 0 {
+            return Err(ErrorCode::CooldownRequiresNonTransferableShares.into());
+        }
+        self.withdraw_cooldown = withdraw_cooldown;
+        emit!(WithdrawCooldownUpdated { withdraw_cooldown });
+        Ok(())
+    }
 }
 
 #[event]
 pub struct ConfigInitialized {
     pub owner: Pubkey,
 }
+
+#[event]
+pub struct DepositCapUpdated {
+    pub deposit_cap: u64,
+}
+
+#[event]
+pub struct UserDepositLimitDefaultUpdated {
+    pub user_deposit_limit: u64,
+}
+
+#[event]
+pub struct AllowlistUpdated {
+    pub enabled: bool,
+}
+
+#[event]
+pub struct WithdrawCooldownUpdated {
+    pub withdraw_cooldown: u64,
+}
+
+#[event]
+pub struct DepositModeUpdated {
+    pub async_deposits: bool,
+}
+
+#[event]
+pub struct CancellationsLockUpdated {
+    pub locked: bool,
+}
+
+#[event]
+pub struct WithdrawRateLimitUpdated {
+    pub bps: u64,
+    pub window: u64,
+}
+
+#[event]
+pub struct WithdrawRateLimitReset {}
+
+#[event]
+pub struct PerformanceFeeUpdated {
+    pub fee_bps: u64,
+    pub fee_recipient: Pubkey,
+}
+
+#[event]
+pub struct ProfitMaxUnlockTimeUpdated {
+    pub profit_max_unlock_time: u64,
+}
+
+#[event]
+pub struct ReportSlotGuardUpdated {
+    pub enabled: bool,
+}
+
+#[event]
+pub struct SharesMetadataUpdated {
+    pub name: String,
+    pub symbol: String,
+    pub uri: String,
+}
+
+#[event]
+pub struct MetadataFieldUpdated {
+    pub key: String,
+    pub value: String,
+}
+
+#[event]
+pub struct ShareMultiplierUpdated {
+    pub multiplier: f64,
+    pub effective_timestamp: i64,
+}
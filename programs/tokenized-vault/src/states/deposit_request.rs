@@ -0,0 +1,181 @@
+use anchor_lang::prelude::*;
+use std::cell::RefMut;
+
+use crate::error::ErrorCode;
+
+#[account(zero_copy)]
+#[derive(InitSpace)]
+pub struct DepositRequest {
+    pub vault: Pubkey,
+    pub user: Pubkey,
+    pub epoch: u64,
+    pub assets: u64,
+    pub _padding2: [u64; 12],
+}
+
+impl DepositRequest {
+    pub fn load_or_init<'a>(
+        loader: &'a AccountLoader<DepositRequest>,
+        vault: Pubkey,
+        user: Pubkey,
+    ) -> Result<RefMut<'a, DepositRequest>> {
+        match loader.load_mut() {
+            Ok(r) => Ok(r),
+            Err(_err) => {
+                let mut request = loader.load_init()?;
+                request.vault = vault;
+                request.user = user;
+                Ok(request)
+            }
+        }
+    }
+
+    pub fn request(&mut self, assets: u64, epoch: u64) -> Result<()> {
+        if self.assets > 0 && self.epoch != epoch {
+            return Err(ErrorCode::UnclaimedDepositRequest.into());
+        }
+        self.epoch = epoch;
+        self.assets = self.assets.saturating_add(assets);
+        emit!(DepositRequested {
+            user: self.user,
+            epoch,
+            assets,
+        });
+        Ok(())
+    }
+
+    pub fn is_claimable(&self, current_epoch: u64) -> bool {
+        self.assets > 0 && self.epoch < current_epoch
+    }
+
+    pub fn pending_assets(&self, current_epoch: u64) -> u64 {
+        if self.epoch == current_epoch {
+            self.assets
+        } else {
+            0
+        }
+    }
+
+    pub fn claimable_assets(&self, current_epoch: u64) -> u64 {
+        if self.epoch < current_epoch {
+            self.assets
+        } else {
+            0
+        }
+    }
+
+    /// Withdraws a request that has not been settled yet and returns its assets.
+    pub fn cancel(&mut self, current_epoch: u64) -> Result<u64> {
+        let assets = self.pending_assets(current_epoch);
+        if assets == 0 {
+            return Err(ErrorCode::NoPendingDepositRequest.into());
+        }
+        emit!(DepositRequestCancelled {
+            user: self.user,
+            epoch: self.epoch,
+            assets,
+        });
+        self.assets = 0;
+        Ok(assets)
+    }
+
+    /// Consumes the request and returns the shares owed at the epoch's settlement rate.
+    pub fn claim(&mut self, current_epoch: u64, deposit_epoch: &mut DepositEpoch) -> Result<u64> {
+        if !self.is_claimable(current_epoch) {
+            return Err(ErrorCode::DepositNotSettled.into());
+        }
+        let shares = deposit_epoch.claim(self.assets);
+        emit!(DepositClaimed {
+            user: self.user,
+            epoch: self.epoch,
+            assets: self.assets,
+            shares,
+        });
+        self.assets = 0;
+        Ok(shares)
+    }
+}
+
+#[account(zero_copy)]
+#[derive(InitSpace)]
+pub struct DepositEpoch {
+    pub vault: Pubkey,
+    pub epoch: u64,
+    pub assets: u64,
+    pub shares: u64,
+    pub settled_at: i64,
+    /// Assets of the requests claimed so far
+    pub claimed_assets: u64,
+    /// Shares paid out of the escrow so far
+    pub claimed_shares: u64,
+    pub _padding2: [u64; 6],
+}
+
+impl DepositEpoch {
+    pub fn settle(
+        &mut self,
+        vault: Pubkey,
+        epoch: u64,
+        assets: u64,
+        shares: u64,
+        now: i64,
+    ) -> Result<()> {
+        self.vault = vault;
+        self.epoch = epoch;
+        self.assets = assets;
+        self.shares = shares;
+        self.settled_at = now;
+        emit!(DepositsSettled {
+            epoch,
+            assets,
+            shares,
+        });
+        Ok(())
+    }
+
+    pub fn shares_for(&self, assets: u64) -> u64 {
+        if self.assets == 0 {
+            return 0;
+        }
+        (assets as u128 * self.shares as u128 / self.assets as u128) as u64
+    }
+
+    /// Shares owed to a request of `assets`. Rounding is applied to the cumulative claims, so
+    /// the claimer completing the epoch receives whatever dust is left in the escrow.
+    pub fn claim(&mut self, assets: u64) -> u64 {
+        let claimed_assets = self.claimed_assets + assets;
+        let shares = self.shares_for(claimed_assets) - self.claimed_shares;
+        self.claimed_assets = claimed_assets;
+        self.claimed_shares += shares;
+        shares
+    }
+}
+
+#[event]
+pub struct DepositRequested {
+    pub user: Pubkey,
+    pub epoch: u64,
+    pub assets: u64,
+}
+
+#[event]
+pub struct DepositRequestCancelled {
+    pub user: Pubkey,
+    pub epoch: u64,
+    pub assets: u64,
+}
+
+#[event]
+pub struct DepositsSettled {
+    pub epoch: u64,
+    pub assets: u64,
+    pub shares: u64,
+}
+
+#[event]
+pub struct DepositClaimed {
+    pub user: Pubkey,
+    pub epoch: u64,
+    pub assets: u64,
+    pub shares: u64,
+}
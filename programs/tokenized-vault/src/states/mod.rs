@@ -3,3 +3,18 @@ pub use access::*;
 
 pub mod config;
 pub use config::*;
+
+pub mod user_position;
+pub use user_position::*;
+
+pub mod blocked;
+pub use blocked::*;
+
+pub mod redeem_request;
+pub use redeem_request::*;
+
+pub mod deposit_request;
+pub use deposit_request::*;
+
+pub mod strategy;
+pub use strategy::*;
@@ -0,0 +1,218 @@
+use anchor_lang::prelude::*;
+use std::cell::RefMut;
+
+use crate::error::ErrorCode;
+
+#[account(zero_copy)]
+#[derive(InitSpace)]
+pub struct RedeemRequest {
+    pub vault: Pubkey,
+    pub user: Pubkey,
+    pub epoch: u64,
+    pub pending_shares: u64,
+    pub claimable_shares: u64,
+    pub claimable_assets: u64,
+    pub requested_at: i64,
+    pub _padding2: [u64; 11],
+}
+
+impl RedeemRequest {
+    pub fn load_or_init<'a>(
+        loader: &'a AccountLoader<RedeemRequest>,
+        vault: Pubkey,
+        user: Pubkey,
+    ) -> Result<RefMut<'a, RedeemRequest>> {
+        match loader.load_mut() {
+            Ok(r) => Ok(r),
+            Err(_err) => {
+                let mut request = loader.load_init()?;
+                request.vault = vault;
+                request.user = user;
+                Ok(request)
+            }
+        }
+    }
+
+    pub fn request(&mut self, shares: u64, current_epoch: u64, now: i64) -> Result<()> {
+        if self.pending_shares > 0 && self.epoch != current_epoch {
+            return Err(ErrorCode::UnclaimedRedeemRequest.into());
+        }
+        self.epoch = current_epoch;
+        self.pending_shares = self.pending_shares.saturating_add(shares);
+        self.requested_at = now;
+        emit!(RedeemRequested {
+            user: self.user,
+            epoch: current_epoch,
+            shares,
+        });
+        Ok(())
+    }
+
+    /// Withdraws the shares queued in the current epoch and returns them.
+    pub fn cancel(&mut self, current_epoch: u64) -> Result<u64> {
+        let shares = self.pending_shares;
+        if shares == 0 {
+            return Err(ErrorCode::NoPendingRedeemRequest.into());
+        }
+        if self.epoch != current_epoch {
+            return Err(ErrorCode::UnclaimedRedeemRequest.into());
+        }
+        emit!(RedeemRequestCancelled {
+            user: self.user,
+            epoch: self.epoch,
+            shares,
+        });
+        self.pending_shares = 0;
+        Ok(shares)
+    }
+
+    /// Applies the pro-rata fill of a closed epoch. Unfilled shares roll into the next epoch.
+    pub fn process_epoch(&mut self, withdrawal_epoch: &mut WithdrawalEpoch) -> Result<()> {
+        if withdrawal_epoch.epoch != self.epoch {
+            return Err(ErrorCode::InvalidWithdrawalEpoch.into());
+        }
+        let (shares, assets, remainder) = withdrawal_epoch.record_fill(self.pending_shares);
+        self.pending_shares = remainder;
+        self.claimable_shares = self.claimable_shares.saturating_add(shares);
+        self.claimable_assets = self.claimable_assets.saturating_add(assets);
+        self.epoch += 1;
+        emit!(RedeemFulfilled {
+            user: self.user,
+            epoch: withdrawal_epoch.epoch,
+            shares,
+            assets,
+        });
+        Ok(())
+    }
+
+    pub fn has_claimable(&self) -> bool {
+        self.claimable_shares > 0 || self.claimable_assets > 0
+    }
+
+    pub fn claim(&mut self) -> Result<u64> {
+        if !self.has_claimable() {
+            return Err(ErrorCode::NoClaimableRedeemRequest.into());
+        }
+        let assets = self.claimable_assets;
+        emit!(RedeemClaimed {
+            user: self.user,
+            shares: self.claimable_shares,
+            assets,
+        });
+        self.claimable_shares = 0;
+        self.claimable_assets = 0;
+        Ok(assets)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.pending_shares == 0 && !self.has_claimable()
+    }
+}
+
+#[account(zero_copy)]
+#[derive(InitSpace)]
+pub struct WithdrawalEpoch {
+    pub vault: Pubkey,
+    pub epoch: u64,
+    pub requested_shares: u64,
+    pub filled_shares: u64,
+    pub filled_assets: u64,
+    pub closed_at: i64,
+    /// Requested shares of the requests that processed the epoch so far
+    pub processed_shares: u64,
+    /// Filled shares attributed to those requests
+    pub processed_filled_shares: u64,
+    /// Assets attributed to those requests
+    pub processed_assets: u64,
+    pub _padding2: [u64; 5],
+}
+
+impl WithdrawalEpoch {
+    pub fn close(
+        &mut self,
+        vault: Pubkey,
+        epoch: u64,
+        requested_shares: u64,
+        filled_shares: u64,
+        filled_assets: u64,
+        now: i64,
+    ) -> Result<()> {
+        self.vault = vault;
+        self.epoch = epoch;
+        self.requested_shares = requested_shares;
+        self.filled_shares = filled_shares;
+        self.filled_assets = filled_assets;
+        self.closed_at = now;
+        emit!(WithdrawalEpochClosed {
+            epoch,
+            requested_shares,
+            filled_shares,
+            filled_assets,
+        });
+        Ok(())
+    }
+
+    /// Splits the next request of `shares` into `(filled_shares, assets, remainder)`.
+    ///
+    /// The pro-rata split is rounded down on the running total of the processed requests, so
+    /// once every request has processed the epoch the fills add up to `filled_shares` and
+    /// `filled_assets` and the remainders to the shares left in escrow.
+    pub fn fill(&self, shares: u64) -> (u64, u64, u64) {
+        if self.requested_shares == 0 {
+            return (0, 0, shares);
+        }
+        let requested = self.requested_shares as u128;
+        let processed = (self.processed_shares + shares) as u128;
+        let filled = (processed * self.filled_shares as u128 / requested) as u64
+            - self.processed_filled_shares;
+        let assets =
+            (processed * self.filled_assets as u128 / requested) as u64 - self.processed_assets;
+        (filled, assets, shares - filled)
+    }
+
+    /// Like `fill`, but records the request as processed.
+    pub fn record_fill(&mut self, shares: u64) -> (u64, u64, u64) {
+        let (filled, assets, remainder) = self.fill(shares);
+        self.processed_shares += shares;
+        self.processed_filled_shares += filled;
+        self.processed_assets += assets;
+        (filled, assets, remainder)
+    }
+}
+
+#[event]
+pub struct RedeemRequested {
+    pub user: Pubkey,
+    pub epoch: u64,
+    pub shares: u64,
+}
+
+#[event]
+pub struct RedeemRequestCancelled {
+    pub user: Pubkey,
+    pub epoch: u64,
+    pub shares: u64,
+}
+
+#[event]
+pub struct WithdrawalEpochClosed {
+    pub epoch: u64,
+    pub requested_shares: u64,
+    pub filled_shares: u64,
+    pub filled_assets: u64,
+}
+
+#[event]
+pub struct RedeemFulfilled {
+    pub user: Pubkey,
+    pub epoch: u64,
+    pub shares: u64,
+    pub assets: u64,
+}
+
+#[event]
+pub struct RedeemClaimed {
+    pub user: Pubkey,
+    pub shares: u64,
+    pub assets: u64,
+}
@@ -0,0 +1,90 @@
+use anchor_lang::prelude::*;
+
+#[account(zero_copy)]
+#[derive(InitSpace)]
+pub struct Strategy {
+    pub vault: Pubkey,
+    pub strategy_program: Pubkey,
+    /// Token account of the strategy receiving the vault's assets
+    pub asset_account: Pubkey,
+    pub current_debt: u64,
+    pub max_debt: u64,
+    pub last_report: i64,
+    pub _padding2: [u64; 8],
+}
+
+impl Strategy {
+    pub fn initialize(
+        &mut self,
+        vault: Pubkey,
+        strategy_program: Pubkey,
+        asset_account: Pubkey,
+        max_debt: u64,
+        now: i64,
+    ) -> Result<()> {
+        self.vault = vault;
+        self.strategy_program = strategy_program;
+        self.asset_account = asset_account;
+        self.max_debt = max_debt;
+        self.last_report = now;
+        emit!(StrategyAdded {
+            strategy_program,
+            asset_account,
+            max_debt,
+        });
+        Ok(())
+    }
+
+    /// Records the assets reported by the strategy as its new debt and returns the
+    /// `(gain, loss)` against the previous debt.
+    pub fn report(&mut self, total_assets: u64, now: i64) -> (u64, u64) {
+        let gain = total_assets.saturating_sub(self.current_debt);
+        let loss = self.current_debt.saturating_sub(total_assets);
+        self.current_debt = total_assets;
+        self.last_report = now;
+        (gain, loss)
+    }
+
+    pub fn set_max_debt(&mut self, max_debt: u64) -> Result<()> {
+        self.max_debt = max_debt;
+        emit!(StrategyMaxDebtUpdated {
+            strategy_program: self.strategy_program,
+            max_debt,
+        });
+        Ok(())
+    }
+}
+
+#[event]
+pub struct StrategyAdded {
+    pub strategy_program: Pubkey,
+    pub asset_account: Pubkey,
+    pub max_debt: u64,
+}
+
+#[event]
+pub struct StrategyRemoved {
+    pub strategy_program: Pubkey,
+}
+
+#[event]
+pub struct StrategyMaxDebtUpdated {
+    pub strategy_program: Pubkey,
+    pub max_debt: u64,
+}
+
+#[event]
+pub struct DebtUpdated {
+    pub strategy_program: Pubkey,
+    pub current_debt: u64,
+    pub new_debt: u64,
+}
+
+#[event]
+pub struct StrategyReported {
+    pub strategy_program: Pubkey,
+    pub gain: u64,
+    pub loss: u64,
+    pub fees: u64,
+    pub total_assets: u64,
+}
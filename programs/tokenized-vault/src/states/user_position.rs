@@ -0,0 +1,116 @@
+use anchor_lang::prelude::*;
+use std::cell::RefMut;
+
+use crate::error::ErrorCode;
+
+#[account(zero_copy)]
+#[derive(InitSpace)]
+pub struct UserPosition {
+    pub vault: Pubkey,
+    pub user: Pubkey,
+    pub net_deposits: u64,
+    pub deposit_limit: u64,
+    pub has_deposit_limit: u8,
+    pub _padding1: [u8; 7],
+    pub last_deposit_ts: i64,
+    pub last_deposit_slot: u64,
+    pub _padding2: [u64; 14],
+}
+
+impl UserPosition {
+    pub fn load_or_init<'a>(
+        loader: &'a AccountLoader<UserPosition>,
+        vault: Pubkey,
+        user: Pubkey,
+    ) -> Result<RefMut<'a, UserPosition>> {
+        match loader.load_mut() {
+            Ok(r) => Ok(r),
+            Err(_err) => {
+                let mut position = loader.load_init()?;
+                position.initialize(vault, user)?;
+                Ok(position)
+            }
+        }
+    }
+
+    pub fn initialize(&mut self, vault: Pubkey, user: Pubkey) -> Result<()> {
+        self.vault = vault;
+        self.user = user;
+        emit!(UserPositionInitialized { vault, user });
+        Ok(())
+    }
+
+    pub fn deposit_limit(&self, default_limit: u64) -> u64 {
+        if self.has_deposit_limit != 0 {
+            self.deposit_limit
+        } else {
+            default_limit
+        }
+    }
+
+    pub fn max_deposit(&self, default_limit: u64) -> u64 {
+        self.deposit_limit(default_limit)
+            .saturating_sub(self.net_deposits)
+    }
+
+    pub fn check_deposit_limit(&self, default_limit: u64, assets: u64) -> Result<()> {
+        if assets > self.max_deposit(default_limit) {
+            return Err(ErrorCode::UserDepositLimitExceeded.into());
+        }
+        Ok(())
+    }
+
+    pub fn record_deposit(&mut self, assets: u64, clock: &Clock) -> Result<()> {
+        self.net_deposits = self.net_deposits.saturating_add(assets);
+        self.last_deposit_ts = clock.unix_timestamp;
+        self.last_deposit_slot = clock.slot;
+        Ok(())
+    }
+
+    pub fn check_same_slot(&self, slot: u64) -> Result<()> {
+        if self.last_deposit_slot == slot {
+            return Err(ErrorCode::SameSlotWithdrawal.into());
+        }
+        Ok(())
+    }
+
+    pub fn is_in_cooldown(&self, withdraw_cooldown: u64, now: i64) -> bool {
+        now < self
+            .last_deposit_ts
+            .saturating_add(withdraw_cooldown.min(i64::MAX as u64) as i64)
+    }
+
+    pub fn check_cooldown(&self, withdraw_cooldown: u64, now: i64) -> Result<()> {
+        if self.is_in_cooldown(withdraw_cooldown, now) {
+            return Err(ErrorCode::CooldownActive.into());
+        }
+        Ok(())
+    }
+
+    pub fn record_withdrawal(&mut self, assets: u64) -> Result<()> {
+        self.net_deposits = self.net_deposits.saturating_sub(assets);
+        Ok(())
+    }
+
+    pub fn set_deposit_limit(&mut self, deposit_limit: Option<u64>) -> Result<()> {
+        self.has_deposit_limit = deposit_limit.is_some() as u8;
+        self.deposit_limit = deposit_limit.unwrap_or_default();
+        emit!(UserDepositLimitUpdated {
+            user: self.user,
+            deposit_limit,
+        });
+        Ok(())
+    }
+}
+
+#[event]
+pub struct UserPositionInitialized {
+    pub vault: Pubkey,
+    pub user: Pubkey,
+}
+
+#[event]
+pub struct UserDepositLimitUpdated {
+    pub user: Pubkey,
+    pub deposit_limit: Option<u64>,
+}
@@ -1,10 +1,11 @@
 use anchor_lang::{
-    prelude::{Result, SolanaSysvar},
+    prelude::{Pubkey, Result, SolanaSysvar},
     solana_program::{
         account_info::AccountInfo, program::invoke, rent::Rent, system_instruction::transfer,
     },
     Lamports,
 };
+use anchor_spl::token_interface::TokenAccount;
 
 pub fn update_account_lamports_to_minimum_balance<'info>(
     account: AccountInfo<'info>,
@@ -22,3 +23,9 @@ pub fn update_account_lamports_to_minimum_balance<'info>(
     }
     Ok(())
 }
+
+/// Whether `authority` can move funds out of `account`, either as its owner or
+/// as its approved delegate.
+pub fn is_owner_or_delegate(account: &TokenAccount, authority: &Pubkey) -> bool {
+    account.owner == *authority || account.delegate.contains(authority)
+}
@@ -1,5 +1,44 @@
+use anchor_lang::prelude::*;
+
 pub const ACCESS_SEED: &str = "access";
 pub const CONFIG_SEED: &str = "config";
 pub const SHARES_MINT_SEED: &str = "shares_mint";
+pub const USER_POSITION_SEED: &str = "user_position";
+pub const BLOCKED_SEED: &str = "blocked";
+pub const REDEEM_REQUEST_SEED: &str = "redeem_request";
+pub const REDEEM_ESCROW_SEED: &str = "redeem_escrow";
+pub const WITHDRAWAL_EPOCH_SEED: &str = "withdrawal_epoch";
+pub const DEPOSIT_REQUEST_SEED: &str = "deposit_request";
+pub const DEPOSIT_EPOCH_SEED: &str = "deposit_epoch";
+pub const DEPOSIT_ESCROW_SEED: &str = "deposit_escrow";
+pub const UNWRAP_SEED: &str = "unwrap";
+pub const STRATEGY_SEED: &str = "strategy";
+pub const LOCKED_SHARES_SEED: &str = "locked_shares";
+
+/// Program the shares mint's transfer hook points to, see `programs/shares-transfer-hook`
+pub const SHARES_TRANSFER_HOOK_PROGRAM_ID: Pubkey =
+    pubkey!("62FUtUf67JgPpdxj8GZDzzM5mhysBmHJDunVoxbWuSsF");
+
+pub const VERSION: &str = env!("CARGO_PKG_VERSION");
 
 pub const MAX_DECIMALS: u8 = 9;
+pub const BPS_DENOMINATOR: u64 = 10_000;
+/// One year, in seconds
+pub const MAX_PROFIT_UNLOCK_TIME: u64 = 31_556_952;
+
+pub const LIMIT_MANAGER_PERMISSION: u128 = 1 << 0;
+pub const WHITELISTED_PERMISSION: u128 = 1 << 1;
+pub const COMPLIANCE_PERMISSION: u128 = 1 << 2;
+pub const KEEPER_PERMISSION: u128 = 1 << 3;
+pub const GUARDIAN_PERMISSION: u128 = 1 << 4;
+pub const METADATA_MANAGER_PERMISSION: u128 = 1 << 5;
+pub const STRATEGY_MANAGER_PERMISSION: u128 = 1 << 6;
+pub const DEBT_MANAGER_PERMISSION: u128 = 1 << 7;
+
+pub const ALLOW_ASSET_FREEZE_AUTHORITY: u16 = 1 << 0;
+pub const ALLOW_ASSET_PERMANENT_DELEGATE: u16 = 1 << 1;
+pub const ALLOW_ASSET_CONFIDENTIAL_TRANSFERS: u16 = 1 << 3;
+pub const SHARES_TRANSFER_HOOK: u16 = 1 << 4;
+pub const SHARES_NON_TRANSFERABLE: u16 = 1 << 5;
+pub const SHARES_SCALED_UI_AMOUNT: u16 = 1 << 6;
+pub const SHARES_CONFIDENTIAL_TRANSFERS: u16 = 1 << 7;
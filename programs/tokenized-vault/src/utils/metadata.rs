@@ -0,0 +1,53 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{
+    spl_token_metadata_interface::state::Field, token_metadata_update_field,
+    TokenMetadataUpdateField,
+};
+
+use crate::states::Config;
+use crate::utils::SHARES_MINT_SEED;
+
+/// Writes a field of the shares mint's Token-2022 metadata, signed by the mint PDA
+/// (its own update authority). The caller tops up rent for any growth.
+pub fn update_shares_metadata_field<'info>(
+    token2022_program: AccountInfo<'info>,
+    shares_mint: AccountInfo<'info>,
+    bump: u8,
+    field: Field,
+    value: String,
+) -> Result<()> {
+    let seeds = &[SHARES_MINT_SEED.as_bytes(), &[bump]];
+    let signer = &[&seeds[..]];
+    token_metadata_update_field(
+        CpiContext::new_with_signer(
+            token2022_program.clone(),
+            TokenMetadataUpdateField {
+                program_id: token2022_program,
+                metadata: shares_mint.clone(),
+                update_authority: shares_mint,
+            },
+            signer,
+        ),
+        field,
+        value,
+    )
+}
+
+/// Mirrors `Config::metadata_fields` into the shares metadata.
+pub fn publish_vault_metadata<'info>(
+    token2022_program: AccountInfo<'info>,
+    shares_mint: AccountInfo<'info>,
+    bump: u8,
+    config: &Config,
+) -> Result<()> {
+    for (key, value) in config.metadata_fields() {
+        update_shares_metadata_field(
+            token2022_program.clone(),
+            shares_mint.clone(),
+            bump,
+            Field::Key(key.to_string()),
+            value,
+        )?;
+    }
+    Ok(())
+}
@@ -0,0 +1,61 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_2022::spl_token_2022::{
+    self,
+    extension::{BaseStateWithExtensions, ExtensionType, StateWithExtensions},
+};
+use anchor_spl::token_interface::Mint;
+
+use crate::error::ErrorCode;
+use crate::utils::{
+    ALLOW_ASSET_CONFIDENTIAL_TRANSFERS, ALLOW_ASSET_FREEZE_AUTHORITY,
+    ALLOW_ASSET_PERMANENT_DELEGATE,
+};
+
+fn check_allowed(options: u16, flag: u16, error: ErrorCode) -> Result<()> {
+    if options & flag == 0 {
+        return Err(error.into());
+    }
+    Ok(())
+}
+
+/// Rejects asset mint features that can freeze, seize or block the vault's assets,
+/// unless explicitly allowed in `options`. Transfer hooks are always rejected: the vault
+/// does not forward the extra accounts of the hook, so none of its transfers would go through.
+pub fn check_asset_mint(mint: &InterfaceAccount<Mint>, options: u16) -> Result<()> {
+    if mint.freeze_authority.is_some() {
+        check_allowed(
+            options,
+            ALLOW_ASSET_FREEZE_AUTHORITY,
+            ErrorCode::AssetMintFreezeAuthority,
+        )?;
+    }
+
+    let info = mint.to_account_info();
+    if info.owner != &spl_token_2022::ID {
+        return Ok(());
+    }
+    let data = info.try_borrow_data()?;
+    let state = StateWithExtensions::<spl_token_2022::state::Mint>::unpack(&data)?;
+    for extension in state.get_extension_types()? {
+        match extension {
+            ExtensionType::NonTransferable => {
+                return Err(ErrorCode::AssetMintNonTransferable.into());
+            }
+            ExtensionType::PermanentDelegate => check_allowed(
+                options,
+                ALLOW_ASSET_PERMANENT_DELEGATE,
+                ErrorCode::AssetMintPermanentDelegate,
+            )?,
+            ExtensionType::TransferHook => {
+                return Err(ErrorCode::AssetMintTransferHook.into());
+            }
+            ExtensionType::ConfidentialTransferMint => check_allowed(
+                options,
+                ALLOW_ASSET_CONFIDENTIAL_TRANSFERS,
+                ErrorCode::AssetMintConfidentialTransfers,
+            )?,
+            _ => {}
+        }
+    }
+    Ok(())
+}
@@ -2,6 +2,7 @@ pub mod constants;
 pub use constants::*;
 
 pub mod account;
+pub use account::*;
 
 pub mod shares_math;
 pub use shares_math::*;
@@ -11,3 +12,15 @@ pub use big_num::*;
 
 pub mod full_math;
 pub use full_math::*;
+
+pub mod transfer_fee;
+pub use transfer_fee::*;
+
+pub mod mint_extensions;
+pub use mint_extensions::*;
+
+pub mod transfer_hook;
+pub use transfer_hook::*;
+
+pub mod metadata;
+pub use metadata::*;
@@ -66,6 +66,26 @@ pub fn preview_redeem_internal(
     Ok(assets)
 }
 
+pub fn max_mint_internal(
+    max_assets: u64,
+    total_assets: u64,
+    total_shares: u64,
+    decimals_offset: u8,
+) -> Result<u64> {
+    let offset = 10u64.pow(decimals_offset as u32);
+    let shares = U128::from(max_assets)
+        .mul_div(
+            U128::from(total_shares + offset),
+            U128::from(total_assets + 1),
+            Rounding::Floor,
+        )
+        .unwrap();
+    if shares > U128::from(u64::MAX) {
+        return Ok(u64::MAX);
+    }
+    Ok(shares.as_u64())
+}
+
 pub fn convert_to_shares(
     assets: u64,
     total_assets: u64,
@@ -120,3 +140,10 @@ pub fn convert_to_assets(
     );
     Ok(x)
 }
+
+/// Scaled-UI-amount multiplier under which a share displays as the assets it converts to,
+/// i.e. `convert_to_assets` of one whole share expressed in whole asset units.
+pub fn share_ui_multiplier(total_assets: u64, total_shares: u64, decimals_offset: u8) -> f64 {
+    let offset = 10u64.pow(decimals_offset as u32) as f64;
+    (total_assets as f64 + 1.0) / (total_shares as f64 + offset) * offset
+}
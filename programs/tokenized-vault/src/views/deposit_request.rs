@@ -0,0 +1,33 @@
+use anchor_lang::prelude::*;
+
+use crate::states::{Config, DepositRequest};
+use crate::utils::{CONFIG_SEED, DEPOSIT_REQUEST_SEED};
+
+#[derive(Accounts)]
+pub struct DepositRequestView<'info> {
+    /// CHECK: Owner of the deposit request
+    pub user: UncheckedAccount<'info>,
+    #[account(seeds = [CONFIG_SEED.as_bytes()], bump)]
+    pub config: AccountLoader<'info, Config>,
+    #[account(
+        seeds = [DEPOSIT_REQUEST_SEED.as_bytes(), config.key().as_ref(), user.key().as_ref()],
+        bump
+    )]
+    pub deposit_request: Option<AccountLoader<'info, DepositRequest>>,
+}
+
+pub fn process_pending_deposit_request(ctx: Context<DepositRequestView>) -> Result<u64> {
+    let current_epoch = ctx.accounts.config.load()?.deposit_epoch;
+    match &ctx.accounts.deposit_request {
+        Some(deposit_request) => Ok(deposit_request.load()?.pending_assets(current_epoch)),
+        None => Ok(0),
+    }
+}
+
+pub fn process_claimable_deposit_request(ctx: Context<DepositRequestView>) -> Result<u64> {
+    let current_epoch = ctx.accounts.config.load()?.deposit_epoch;
+    match &ctx.accounts.deposit_request {
+        Some(deposit_request) => Ok(deposit_request.load()?.claimable_assets(current_epoch)),
+        None => Ok(0),
+    }
+}
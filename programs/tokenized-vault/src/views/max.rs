@@ -0,0 +1,121 @@
+use anchor_lang::prelude::*;
+
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token_interface::{Mint, Token2022, TokenAccount, TokenInterface};
+
+use crate::states::{Config, UserPosition};
+use crate::utils::{
+    amount_after_fee, max_mint_internal, preview_redeem_internal, CONFIG_SEED, SHARES_MINT_SEED,
+    USER_POSITION_SEED,
+};
+
+#[derive(Accounts)]
+pub struct MaxPreview<'info> {
+    #[account(mint::token_program = asset_token_program)]
+    pub asset_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(associated_token::mint = asset_mint, associated_token::authority = shares_mint, associated_token::token_program = asset_token_program)]
+    pub asset_vault: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(seeds = [SHARES_MINT_SEED.as_bytes()], bump, mint::token_program = token2022_program)]
+    pub shares_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(seeds = [CONFIG_SEED.as_bytes()], bump, has_one = asset_mint)]
+    pub config: AccountLoader<'info, Config>,
+
+    /// CHECK: This is the account whose limits are queried
+    pub user: UncheckedAccount<'info>,
+    #[account(seeds = [USER_POSITION_SEED.as_bytes(), config.key().as_ref(), user.key().as_ref()], bump)]
+    pub user_position: Option<AccountLoader<'info, UserPosition>>,
+    #[account(associated_token::mint = shares_mint, associated_token::authority = user, associated_token::token_program = token2022_program)]
+    pub user_shares_vault: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    pub asset_token_program: Interface<'info, TokenInterface>,
+    pub token2022_program: Program<'info, Token2022>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn process_max_deposit(ctx: Context<MaxPreview>) -> Result<u64> {
+    ctx.accounts.max_deposit()
+}
+
+pub fn process_max_mint(ctx: Context<MaxPreview>) -> Result<u64> {
+    let config = ctx.accounts.config.load()?;
+    let shares = max_mint_internal(
+        ctx.accounts.max_deposit()?,
+        config.total_assets(ctx.accounts.asset_vault.amount),
+        config.total_supply(
+            ctx.accounts.shares_mint.supply,
+            Clock::get()?.unix_timestamp,
+        ),
+        config.decimals_offset,
+    )?;
+    Ok(shares)
+}
+
+pub fn process_max_withdraw(ctx: Context<MaxPreview>) -> Result<u64> {
+    let config = ctx.accounts.config.load()?;
+    let now = Clock::get()?.unix_timestamp;
+    let total_assets = config.total_assets(ctx.accounts.asset_vault.amount);
+    let assets = preview_redeem_internal(
+        ctx.accounts.max_redeem()?,
+        total_assets,
+        config.total_supply(ctx.accounts.shares_mint.supply, now),
+        config.decimals_offset,
+    )?;
+    let assets = assets
+        .min(config.max_withdraw(total_assets, now))
+        .min(config.idle_assets(ctx.accounts.asset_vault.amount));
+    amount_after_fee(&ctx.accounts.asset_mint.to_account_info(), assets)
+}
+
+pub fn process_max_redeem(ctx: Context<MaxPreview>) -> Result<u64> {
+    let config = ctx.accounts.config.load()?;
+    let now = Clock::get()?.unix_timestamp;
+    let total_assets = config.total_assets(ctx.accounts.asset_vault.amount);
+    let rate_limited_shares = max_mint_internal(
+        config
+            .max_withdraw(total_assets, now)
+            .min(config.idle_assets(ctx.accounts.asset_vault.amount)),
+        total_assets,
+        config.total_supply(ctx.accounts.shares_mint.supply, now),
+        config.decimals_offset,
+    )?;
+    Ok(ctx.accounts.max_redeem()?.min(rate_limited_shares))
+}
+
+impl<'info> MaxPreview<'info> {
+    pub fn max_deposit(&self) -> Result<u64> {
+        let config = self.config.load()?;
+        let user_max_deposit = match &self.user_position {
+            Some(user_position) => user_position
+                .load()?
+                .max_deposit(config.default_deposit_limit()),
+            None => config.default_deposit_limit(),
+        };
+        Ok(config
+            .max_deposit(config.total_assets(self.asset_vault.amount))
+            .min(user_max_deposit))
+    }
+
+    pub fn max_redeem(&self) -> Result<u64> {
+        let Some(user_shares_vault) = &self.user_shares_vault else {
+            return Ok(0);
+        };
+        let config = self.config.load()?;
+        let clock = Clock::get()?;
+        if config.is_report_slot(clock.slot) {
+            return Ok(0);
+        }
+        if let Some(user_position) = &self.user_position {
+            let user_position = user_position.load()?;
+            if user_position.is_in_cooldown(config.withdraw_cooldown, clock.unix_timestamp)
+                || user_position.last_deposit_slot == clock.slot
+            {
+                return Ok(0);
+            }
+        }
+        Ok(user_shares_vault.amount)
+    }
+}
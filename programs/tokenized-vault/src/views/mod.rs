@@ -1,2 +1,11 @@
 pub mod preview;
 pub use preview::*;
+
+pub mod max;
+pub use max::*;
+
+pub mod redeem_request;
+pub use redeem_request::*;
+
+pub mod deposit_request;
+pub use deposit_request::*;
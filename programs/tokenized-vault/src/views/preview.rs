@@ -5,8 +5,8 @@ use anchor_spl::token_interface::{Mint, Token2022, TokenAccount, TokenInterface}
 
 use crate::states::Config;
 use crate::utils::{
-    preview_deposit_internal, preview_mint_internal, preview_redeem_internal,
-    preview_withdraw_internal, CONFIG_SEED, SHARES_MINT_SEED,
+    amount_after_fee, amount_before_fee, preview_deposit_internal, preview_mint_internal,
+    preview_redeem_internal, preview_withdraw_internal, CONFIG_SEED, SHARES_MINT_SEED,
 };
 
 #[derive(Accounts)]
@@ -30,41 +30,63 @@ pub struct Preview<'info> {
 }
 
 pub fn process_preview_deposit(ctx: Context<Preview>, assets: u64) -> Result<u64> {
+    let config = ctx.accounts.config.load()?;
+    let now = Clock::get()?.unix_timestamp;
     let shares = preview_deposit_internal(
-        assets,
-        ctx.accounts.asset_vault.amount,
-        ctx.accounts.shares_mint.supply,
-        ctx.accounts.config.load()?.decimals_offset,
+        amount_after_fee(&ctx.accounts.asset_mint.to_account_info(), assets)?,
+        config.total_assets(ctx.accounts.asset_vault.amount),
+        config.total_supply(ctx.accounts.shares_mint.supply, now),
+        config.decimals_offset,
     )?;
     Ok(shares)
 }
 
 pub fn process_preview_mint(ctx: Context<Preview>, shares: u64) -> Result<u64> {
+    let config = ctx.accounts.config.load()?;
+    let now = Clock::get()?.unix_timestamp;
     let assets = preview_mint_internal(
         shares,
-        ctx.accounts.asset_vault.amount,
-        ctx.accounts.shares_mint.supply,
-        ctx.accounts.config.load()?.decimals_offset,
+        config.total_assets(ctx.accounts.asset_vault.amount),
+        config.total_supply(ctx.accounts.shares_mint.supply, now),
+        config.decimals_offset,
     )?;
-    Ok(assets)
+    amount_before_fee(&ctx.accounts.asset_mint.to_account_info(), assets)
 }
 
 pub fn process_preview_withdraw(ctx: Context<Preview>, assets: u64) -> Result<u64> {
+    let config = ctx.accounts.config.load()?;
+    let now = Clock::get()?.unix_timestamp;
     let shares = preview_withdraw_internal(
-        assets,
-        ctx.accounts.asset_vault.amount,
-        ctx.accounts.shares_mint.supply,
-        ctx.accounts.config.load()?.decimals_offset,
+        amount_before_fee(&ctx.accounts.asset_mint.to_account_info(), assets)?,
+        config.total_assets(ctx.accounts.asset_vault.amount),
+        config.total_supply(ctx.accounts.shares_mint.supply, now),
+        config.decimals_offset,
     )?;
     Ok(shares)
 }
 
 pub fn process_preview_redeem(ctx: Context<Preview>, shares: u64) -> Result<u64> {
+    let config = ctx.accounts.config.load()?;
+    let now = Clock::get()?.unix_timestamp;
     let assets = preview_redeem_internal(
         shares,
-        ctx.accounts.asset_vault.amount,
-        ctx.accounts.shares_mint.supply,
-        ctx.accounts.config.load()?.decimals_offset,
+        config.total_assets(ctx.accounts.asset_vault.amount),
+        config.total_supply(ctx.accounts.shares_mint.supply, now),
+        config.decimals_offset,
     )?;
-    Ok(assets)
+    amount_after_fee(&ctx.accounts.asset_mint.to_account_info(), assets)
+}
+
+/// Shares pricing the vault, without the profit shares unlocked since the last report.
+pub fn process_total_supply(ctx: Context<Preview>) -> Result<u64> {
+    let config = ctx.accounts.config.load()?;
+    let now = Clock::get()?.unix_timestamp;
+    Ok(config.total_supply(ctx.accounts.shares_mint.supply, now))
+}
+
+/// Profit shares still locked in the vault.
+pub fn process_locked_shares(ctx: Context<Preview>) -> Result<u64> {
+    let config = ctx.accounts.config.load()?;
+    let now = Clock::get()?.unix_timestamp;
+    Ok(config.locked_shares - config.unlocked_shares(now))
 }
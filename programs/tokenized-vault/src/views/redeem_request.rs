@@ -0,0 +1,63 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::Mint;
+
+use crate::states::{Config, RedeemRequest, WithdrawalEpoch};
+use crate::utils::{amount_after_fee, CONFIG_SEED, REDEEM_REQUEST_SEED};
+
+#[derive(Accounts)]
+pub struct RedeemRequestView<'info> {
+    /// CHECK: Owner of the redeem request
+    pub user: UncheckedAccount<'info>,
+    #[account(seeds = [CONFIG_SEED.as_bytes()], bump, has_one = asset_mint)]
+    pub config: AccountLoader<'info, Config>,
+    pub asset_mint: InterfaceAccount<'info, Mint>,
+    #[account(
+        seeds = [REDEEM_REQUEST_SEED.as_bytes(), config.key().as_ref(), user.key().as_ref()],
+        bump
+    )]
+    pub redeem_request: Option<AccountLoader<'info, RedeemRequest>>,
+    /// Closed epoch the request has not yet processed, if any
+    pub withdrawal_epoch: Option<AccountLoader<'info, WithdrawalEpoch>>,
+}
+
+impl RedeemRequestView<'_> {
+    /// Returns `(pending_shares, claimable_shares, claimable_assets)` as if the next closed
+    /// epoch had been claimed.
+    pub fn project(&self) -> Result<(u64, u64, u64)> {
+        let Some(redeem_request) = &self.redeem_request else {
+            return Ok((0, 0, 0));
+        };
+        let redeem_request = redeem_request.load()?;
+        let (mut pending, mut claimable, mut assets) = (
+            redeem_request.pending_shares,
+            redeem_request.claimable_shares,
+            redeem_request.claimable_assets,
+        );
+        if let Some(withdrawal_epoch) = &self.withdrawal_epoch {
+            let withdrawal_epoch = withdrawal_epoch.load()?;
+            if withdrawal_epoch.epoch == redeem_request.epoch {
+                let (shares, filled_assets, remainder) = withdrawal_epoch.fill(pending);
+                pending = remainder;
+                claimable = claimable.saturating_add(shares);
+                assets = assets.saturating_add(filled_assets);
+            }
+        }
+        Ok((pending, claimable, assets))
+    }
+}
+
+pub fn process_pending_redeem_request(ctx: Context<RedeemRequestView>) -> Result<u64> {
+    Ok(ctx.accounts.project()?.0)
+}
+
+pub fn process_claimable_redeem_request(ctx: Context<RedeemRequestView>) -> Result<u64> {
+    Ok(ctx.accounts.project()?.1)
+}
+
+/// Assets `claim_redeem` pays out, net of the asset's transfer fee.
+pub fn process_claimable_redeem_assets(ctx: Context<RedeemRequestView>) -> Result<u64> {
+    amount_after_fee(
+        &ctx.accounts.asset_mint.to_account_info(),
+        ctx.accounts.project()?.2,
+    )
+}
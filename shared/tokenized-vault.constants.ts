@@ -1,4 +1,5 @@
 import { address as programAddress } from "../target/idl/tokenized_vault.json";
+import { address as hookProgramAddress } from "../target/idl/shares_transfer_hook.json";
 import { PublicKey } from "@solana/web3.js";
 import { utf8 } from "@coral-xyz/anchor/dist/cjs/utils/bytes";
 import { BN } from "@coral-xyz/anchor";
@@ -6,12 +7,43 @@ import { maxUint64 } from "viem";
 
 export const PERMISSIONS = {
   ALL: new BN(maxUint64),
+  LIMIT_MANAGER: new BN(1).shln(0),
+  WHITELISTED: new BN(1).shln(1),
+  COMPLIANCE: new BN(1).shln(2),
+  KEEPER: new BN(1).shln(3),
+  GUARDIAN: new BN(1).shln(4),
+  METADATA_MANAGER: new BN(1).shln(5),
+  STRATEGY_MANAGER: new BN(1).shln(6),
+  DEBT_MANAGER: new BN(1).shln(7),
+};
+
+export const INIT_OPTIONS = {
+  NONE: 0,
+  ALLOW_ASSET_FREEZE_AUTHORITY: 1 << 0,
+  ALLOW_ASSET_PERMANENT_DELEGATE: 1 << 1,
+  ALLOW_ASSET_CONFIDENTIAL_TRANSFERS: 1 << 3,
+  SHARES_TRANSFER_HOOK: 1 << 4,
+  SHARES_NON_TRANSFERABLE: 1 << 5,
+  SHARES_SCALED_UI_AMOUNT: 1 << 6,
+  SHARES_CONFIDENTIAL_TRANSFERS: 1 << 7,
 };
 
 export const SEEDS = {
   CONFIG: "config",
   ACCESS: "access",
   SHARES_MINT: "shares_mint",
+  USER_POSITION: "user_position",
+  BLOCKED: "blocked",
+  REDEEM_REQUEST: "redeem_request",
+  REDEEM_ESCROW: "redeem_escrow",
+  WITHDRAWAL_EPOCH: "withdrawal_epoch",
+  DEPOSIT_REQUEST: "deposit_request",
+  DEPOSIT_EPOCH: "deposit_epoch",
+  DEPOSIT_ESCROW: "deposit_escrow",
+  EXTRA_ACCOUNT_METAS: "extra-account-metas",
+  UNWRAP: "unwrap",
+  STRATEGY: "strategy",
+  LOCKED_SHARES: "locked_shares",
 };
 
 export const ADDRESSES = {
@@ -28,4 +60,85 @@ export const ADDRESSES = {
     [utf8.encode(SEEDS.SHARES_MINT)],
     new PublicKey(programAddress)
   )[0],
+  USER_POSITION: (account: PublicKey) =>
+    PublicKey.findProgramAddressSync(
+      [
+        utf8.encode(SEEDS.USER_POSITION),
+        ADDRESSES.CONFIG.toBuffer(),
+        account.toBuffer(),
+      ],
+      new PublicKey(programAddress)
+    )[0],
+  BLOCKED: (account: PublicKey) =>
+    PublicKey.findProgramAddressSync(
+      [utf8.encode(SEEDS.BLOCKED), account.toBuffer()],
+      new PublicKey(programAddress)
+    )[0],
+  REDEEM_REQUEST: (account: PublicKey) =>
+    PublicKey.findProgramAddressSync(
+      [
+        utf8.encode(SEEDS.REDEEM_REQUEST),
+        ADDRESSES.CONFIG.toBuffer(),
+        account.toBuffer(),
+      ],
+      new PublicKey(programAddress)
+    )[0],
+  REDEEM_ESCROW: PublicKey.findProgramAddressSync(
+    [utf8.encode(SEEDS.REDEEM_ESCROW)],
+    new PublicKey(programAddress)
+  )[0],
+  WITHDRAWAL_EPOCH: (epoch: number) =>
+    PublicKey.findProgramAddressSync(
+      [
+        utf8.encode(SEEDS.WITHDRAWAL_EPOCH),
+        ADDRESSES.CONFIG.toBuffer(),
+        new BN(epoch).toArrayLike(Buffer, "le", 8),
+      ],
+      new PublicKey(programAddress)
+    )[0],
+  DEPOSIT_REQUEST: (account: PublicKey) =>
+    PublicKey.findProgramAddressSync(
+      [
+        utf8.encode(SEEDS.DEPOSIT_REQUEST),
+        ADDRESSES.CONFIG.toBuffer(),
+        account.toBuffer(),
+      ],
+      new PublicKey(programAddress)
+    )[0],
+  DEPOSIT_EPOCH: (epoch: number) =>
+    PublicKey.findProgramAddressSync(
+      [
+        utf8.encode(SEEDS.DEPOSIT_EPOCH),
+        ADDRESSES.CONFIG.toBuffer(),
+        new BN(epoch).toArrayLike(Buffer, "le", 8),
+      ],
+      new PublicKey(programAddress)
+    )[0],
+  DEPOSIT_ESCROW: PublicKey.findProgramAddressSync(
+    [utf8.encode(SEEDS.DEPOSIT_ESCROW)],
+    new PublicKey(programAddress)
+  )[0],
+  EXTRA_ACCOUNT_METAS: (mint: PublicKey) =>
+    PublicKey.findProgramAddressSync(
+      [utf8.encode(SEEDS.EXTRA_ACCOUNT_METAS), mint.toBuffer()],
+      new PublicKey(hookProgramAddress)
+    )[0],
+  UNWRAP: (account: PublicKey) =>
+    PublicKey.findProgramAddressSync(
+      [utf8.encode(SEEDS.UNWRAP), account.toBuffer()],
+      new PublicKey(programAddress)
+    )[0],
+  STRATEGY: (strategyProgram: PublicKey) =>
+    PublicKey.findProgramAddressSync(
+      [
+        utf8.encode(SEEDS.STRATEGY),
+        ADDRESSES.CONFIG.toBuffer(),
+        strategyProgram.toBuffer(),
+      ],
+      new PublicKey(programAddress)
+    )[0],
+  LOCKED_SHARES: PublicKey.findProgramAddressSync(
+    [utf8.encode(SEEDS.LOCKED_SHARES)],
+    new PublicKey(programAddress)
+  )[0],
 };
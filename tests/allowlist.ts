@@ -0,0 +1,71 @@
+import { expect } from 'chai'
+import { PublicKey } from '@solana/web3.js'
+import { createFixture, Fixture } from './fixtures/tokenized-vault-fixture'
+import { ADDRESSES, PERMISSIONS } from '../shared/tokenized-vault.constants'
+
+const accessPairs = (accounts: PublicKey[]) =>
+  accounts.flatMap((account) => [
+    { pubkey: account, isSigner: false, isWritable: false },
+    { pubkey: ADDRESSES.ACCESS(account), isSigner: false, isWritable: true },
+  ])
+
+describe('allowlist', () => {
+  let fixture: Fixture
+
+  beforeEach(async () => {
+    fixture = await createFixture()
+    await fixture.program.methods.setAllowlistEnabled(true).rpc()
+  })
+
+  it('rejects deposit and mint from non-whitelisted users', async () => {
+    const { users, assetMint, sharesMint } = fixture
+    const user = users[0]
+    await expect(
+      user.program.methods.deposit(assetMint.parseAmount(1)).accounts(user.accounts).rpc()
+    ).to.be.rejectedWith('UnauthorizedPermission')
+    await expect(
+      user.program.methods.mint(sharesMint.parseAmount(1)).accounts(user.accounts).rpc()
+    ).to.be.rejectedWith('UnauthorizedPermission')
+  })
+
+  it('onboards users in bulk and lets them deposit', async () => {
+    const { program, provider, users, assetMint, sharesMint, cmmAccounts } = fixture
+    const onboarded = users.slice(0, 3)
+    await program.methods
+      .grantPermissionBatch(PERMISSIONS.WHITELISTED)
+      .remainingAccounts(accessPairs(onboarded.map((user) => user.publicKey)))
+      .rpc()
+
+    for (const user of onboarded) {
+      await expect(
+        user.program.methods
+          .deposit(assetMint.parseAmount(1))
+          .accounts({ ...cmmAccounts, userAccess: ADDRESSES.ACCESS(user.publicKey) })
+          .rpc()
+      ).splBalancesHaveChanged(provider, sharesMint.publicKey, [
+        { wallet: user.publicKey, amount: sharesMint.parseAmount(1) },
+      ])
+    }
+
+    const outsider = users[3]
+    await expect(
+      outsider.program.methods.deposit(assetMint.parseAmount(1)).accounts(outsider.accounts).rpc()
+    ).to.be.rejectedWith('UnauthorizedPermission')
+  })
+
+  it('does not require whitelisting when allowlist mode is disabled', async () => {
+    const { program, users, assetMint } = fixture
+    await program.methods.setAllowlistEnabled(false).rpc()
+    await users[0].program.methods.deposit(assetMint.parseAmount(1)).accounts(users[0].accounts).rpc()
+  })
+
+  it('rejects batch grants with unpaired accounts', async () => {
+    const { program, users } = fixture
+    await expect(
+      program.methods
+        .grantPermissionBatch(PERMISSIONS.WHITELISTED)
+        .remainingAccounts(accessPairs([users[0].publicKey]).slice(0, 1))
+        .rpc()
+    ).to.be.rejectedWith('InvalidRemainingAccounts')
+  })
+})
@@ -0,0 +1,51 @@
+import { expect } from 'chai'
+import { ExtensionType } from '@solana/spl-token'
+import { Keypair } from '@solana/web3.js'
+import { parseUnits } from 'viem'
+import { createFixture, Fixture } from './fixtures/tokenized-vault-fixture'
+import { createTokenAndMint, MintWrapper } from './fixtures/spl'
+import { INIT_OPTIONS } from '../shared/tokenized-vault.constants'
+
+describe('asset-mint validation', () => {
+  const initialize = async (program: Fixture['program'], assetMint: MintWrapper, options: number) =>
+    program.methods
+      .initialize({ name: 'Test Token', symbol: 'TEST', uri: 'https://test.com' }, options)
+      .accounts({ assetMint: assetMint.publicKey, assetTokenProgram: assetMint.tokenProgramId })
+      .rpc()
+
+  it('requires an opt-in for a freeze authority', async () => {
+    const { provider, program } = await createFixture({ createConfig: false })
+    const assetMint = await createTokenAndMint(provider, parseUnits('1000', 6), {
+      freezeAuthority: provider.wallet.publicKey,
+    })
+
+    await expect(initialize(program, assetMint, INIT_OPTIONS.NONE)).to.be.rejectedWith('AssetMintFreezeAuthority')
+    await initialize(program, assetMint, INIT_OPTIONS.ALLOW_ASSET_FREEZE_AUTHORITY)
+  })
+
+  it('requires an opt-in for a permanent delegate', async () => {
+    const { provider, program } = await createFixture({ createConfig: false })
+    const assetMint = await createTokenAndMint(provider, parseUnits('1000', 6), {
+      permanentDelegate: Keypair.generate().publicKey,
+    })
+
+    await expect(initialize(program, assetMint, INIT_OPTIONS.ALLOW_ASSET_FREEZE_AUTHORITY)).to.be.rejectedWith(
+      'AssetMintPermanentDelegate'
+    )
+    await initialize(program, assetMint, INIT_OPTIONS.ALLOW_ASSET_PERMANENT_DELEGATE)
+  })
+
+  it('rejects non-transferable asset mints', async () => {
+    const { provider, program } = await createFixture({ createConfig: false })
+    const assetMint = await createTokenAndMint(provider, 0n, { extensions: [ExtensionType.NonTransferable] })
+
+    await expect(initialize(program, assetMint, 0xffff)).to.be.rejectedWith('AssetMintNonTransferable')
+  })
+
+  it('rejects asset mints with a transfer hook', async () => {
+    const { provider, program } = await createFixture({ createConfig: false })
+    const assetMint = await createTokenAndMint(provider, 0n, { extensions: [ExtensionType.TransferHook] })
+
+    await expect(initialize(program, assetMint, 0xffff)).to.be.rejectedWith('AssetMintTransferHook')
+  })
+})
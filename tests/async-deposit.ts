@@ -0,0 +1,99 @@
+import { expect } from 'chai'
+import BN from 'bn.js'
+import { createFixture, Fixture } from './fixtures/tokenized-vault-fixture'
+import { ADDRESSES, PERMISSIONS } from '../shared/tokenized-vault.constants'
+
+describe('async deposit', () => {
+  let fixture: Fixture
+
+  beforeEach(async () => {
+    fixture = await createFixture()
+    const { program, admin } = fixture
+    await program.methods.grantPermission(PERMISSIONS.KEEPER).accounts({ to: admin.publicKey }).rpc()
+    await program.methods.setAsyncDeposits(true).rpc()
+  })
+
+  it('rejects synchronous deposit and mint in async mode', async () => {
+    const { users, assetMint, sharesMint } = fixture
+    await expect(
+      users[0].program.methods.deposit(assetMint.parseAmount(1)).accounts(users[0].accounts).rpc()
+    ).to.be.rejectedWith('SyncDepositsDisabled')
+    await expect(
+      users[0].program.methods.mint(sharesMint.parseAmount(1)).accounts(users[0].accounts).rpc()
+    ).to.be.rejectedWith('SyncDepositsDisabled')
+  })
+
+  it('settles all pending requests at one rate', async () => {
+    const { provider, program, admin, users, assetMint, sharesMint, cmmAccounts } = fixture
+    const [alice, bob] = users
+
+    await expect(
+      alice.program.methods.requestDeposit(assetMint.parseAmount(10)).accounts(cmmAccounts).rpc()
+    ).splBalancesHaveChanged(provider, assetMint.publicKey, [
+      { wallet: alice.publicKey, amount: -assetMint.parseAmount(10) },
+    ])
+    await bob.program.methods.requestDeposit(assetMint.parseAmount(30)).accounts(cmmAccounts).rpc()
+
+    const viewAccounts = { user: alice.publicKey, depositRequest: ADDRESSES.DEPOSIT_REQUEST(alice.publicKey) }
+    expect((await program.methods.pendingDepositRequest().accounts(viewAccounts).view()).toString()).to.equal(
+      assetMint.parseAmount(10).toString()
+    )
+
+    await admin.program.methods
+      .settleDeposits()
+      .accounts({ ...cmmAccounts, access: ADDRESSES.ACCESS(admin.publicKey), depositEpoch: ADDRESSES.DEPOSIT_EPOCH(0) })
+      .rpc()
+    expect((await program.methods.pendingDepositRequest().accounts(viewAccounts).view()).toString()).to.equal('0')
+    expect((await program.methods.claimableDepositRequest().accounts(viewAccounts).view()).toString()).to.equal(
+      assetMint.parseAmount(10).toString()
+    )
+
+    for (const [user, amount] of [
+      [alice, 10],
+      [bob, 30],
+    ] as const) {
+      await expect(
+        user.program.methods.claimDeposit().accounts({ depositEpoch: ADDRESSES.DEPOSIT_EPOCH(0) }).rpc()
+      ).splBalancesHaveChanged(provider, sharesMint.publicKey, [
+        { wallet: user.publicKey, amount: sharesMint.parseAmount(amount) },
+      ])
+    }
+    expect(provider.client.getAccount(ADDRESSES.DEPOSIT_REQUEST(alice.publicKey))).to.be.null
+  })
+
+  it('pays the rounding dust to the last claimer', async () => {
+    const { provider, admin, users, assetMint, sharesMint, cmmAccounts } = fixture
+    const claimers = users.slice(0, 3)
+    // A donation makes the epoch's shares indivisible between equal requests
+    await assetMint.transferTo(provider, sharesMint.publicKey, 6n)
+    for (const user of claimers) {
+      await user.program.methods.requestDeposit(new BN(1)).accounts(cmmAccounts).rpc()
+    }
+    await admin.program.methods
+      .settleDeposits()
+      .accounts({ ...cmmAccounts, access: ADDRESSES.ACCESS(admin.publicKey), depositEpoch: ADDRESSES.DEPOSIT_EPOCH(0) })
+      .rpc()
+    const settled = sharesMint.balanceOfTokenAccount(ADDRESSES.DEPOSIT_ESCROW)
+    expect(settled % 3n).to.not.equal(0n)
+
+    for (const user of claimers) {
+      await user.program.methods.claimDeposit().accounts({ depositEpoch: ADDRESSES.DEPOSIT_EPOCH(0) }).rpc()
+    }
+    const claimed = claimers.map((user) => sharesMint.balanceOf(user.publicKey))
+    expect(claimed[0]).to.equal(settled / 3n)
+    expect(claimed.reduce((a, b) => a + b)).to.equal(settled)
+    expect(sharesMint.balanceOfTokenAccount(ADDRESSES.DEPOSIT_ESCROW)).to.equal(0n)
+  })
+
+  it('rejects settlement without KEEPER permission', async () => {
+    const { users, assetMint, cmmAccounts } = fixture
+    await users[0].program.methods.requestDeposit(assetMint.parseAmount(10)).accounts(cmmAccounts).rpc()
+
+    await expect(
+      users[1].program.methods
+        .settleDeposits()
+        .accounts({ ...cmmAccounts, access: null, depositEpoch: ADDRESSES.DEPOSIT_EPOCH(0) })
+        .rpc()
+    ).to.be.rejectedWith('UnauthorizedPermission')
+  })
+})
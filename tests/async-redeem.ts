@@ -0,0 +1,185 @@
+import { expect } from 'chai'
+import BN from 'bn.js'
+import { createFixture, Fixture } from './fixtures/tokenized-vault-fixture'
+import { createMockStrategy } from './fixtures/mock-strategy'
+import { nextSlot } from './fixtures/utils'
+import { ADDRESSES, PERMISSIONS } from '../shared/tokenized-vault.constants'
+
+describe('async redeem', () => {
+  let fixture: Fixture
+
+  beforeEach(async () => {
+    fixture = await createFixture()
+    const { program, admin } = fixture
+    await program.methods.grantPermission(PERMISSIONS.KEEPER).accounts({ to: admin.publicKey }).rpc()
+  })
+
+  const closeEpoch = (epoch: number) => {
+    const { admin, cmmAccounts } = fixture
+    return admin.program.methods
+      .closeWithdrawalEpoch()
+      .accounts({
+        ...cmmAccounts,
+        access: ADDRESSES.ACCESS(admin.publicKey),
+        withdrawalEpoch: ADDRESSES.WITHDRAWAL_EPOCH(epoch),
+      })
+      .rpc()
+  }
+
+  it('fills all requests of an epoch at one rate', async () => {
+    const { provider, program, users, assetMint, sharesMint, cmmAccounts } = fixture
+    const [alice, bob] = users
+    await alice.program.methods.deposit(assetMint.parseAmount(10)).accounts(alice.accounts).rpc()
+    await bob.program.methods.deposit(assetMint.parseAmount(30)).accounts(bob.accounts).rpc()
+    nextSlot(provider)
+
+    await expect(
+      alice.program.methods.requestRedeem(sharesMint.parseAmount(4)).accounts(cmmAccounts).rpc()
+    ).splBalancesHaveChanged(provider, sharesMint.publicKey, [
+      { wallet: alice.publicKey, amount: -sharesMint.parseAmount(4) },
+    ])
+    await bob.program.methods.requestRedeem(sharesMint.parseAmount(6)).accounts(cmmAccounts).rpc()
+
+    const viewAccounts = {
+      user: alice.publicKey,
+      redeemRequest: ADDRESSES.REDEEM_REQUEST(alice.publicKey),
+      withdrawalEpoch: null,
+    }
+    expect((await program.methods.pendingRedeemRequest().accounts(viewAccounts).view()).toString()).to.equal(
+      sharesMint.parseAmount(4).toString()
+    )
+
+    await closeEpoch(0)
+    const closedViewAccounts = { ...viewAccounts, withdrawalEpoch: ADDRESSES.WITHDRAWAL_EPOCH(0) }
+    expect((await program.methods.pendingRedeemRequest().accounts(closedViewAccounts).view()).toString()).to.equal('0')
+    expect(
+      (await program.methods.claimableRedeemRequest().accounts(closedViewAccounts).view()).toString()
+    ).to.equal(sharesMint.parseAmount(4).toString())
+
+    // Reserved assets no longer back outstanding shares
+    expect(
+      (await program.methods.previewRedeem(sharesMint.parseAmount(30)).accounts(cmmAccounts).view()).toString()
+    ).to.equal(assetMint.parseAmount(30).toString())
+
+    for (const [user, amount] of [
+      [alice, 4],
+      [bob, 6],
+    ] as const) {
+      await expect(
+        user.program.methods
+          .claimRedeem()
+          .accounts({ ...cmmAccounts, withdrawalEpoch: ADDRESSES.WITHDRAWAL_EPOCH(0) })
+          .rpc()
+      ).splBalancesHaveChanged(provider, assetMint.publicKey, [
+        { wallet: user.publicKey, amount: assetMint.parseAmount(amount) },
+      ])
+    }
+    expect(provider.client.getAccount(ADDRESSES.REDEEM_REQUEST(alice.publicKey))).to.be.null
+  })
+
+  it('splits a partial fill exactly between requests', async () => {
+    const { provider, program, admin, users, assetMint, sharesMint, cmmAccounts } = fixture
+    const requesters = users.slice(0, 3)
+    for (const user of requesters) {
+      await user.program.methods.deposit(assetMint.parseAmount(10)).accounts(user.accounts).rpc()
+    }
+    // Only 10.000001 assets stay idle, which fills a third of the requests plus indivisible dust
+    for (const permission of [PERMISSIONS.STRATEGY_MANAGER, PERMISSIONS.DEBT_MANAGER]) {
+      await program.methods.grantPermission(permission).accounts({ to: admin.publicKey }).rpc()
+    }
+    const mock = await createMockStrategy(fixture)
+    await mock.add(assetMint.parseAmount(30))
+    await mock.updateDebt(assetMint.parseAmount(20).sub(new BN(1)))
+    nextSlot(provider)
+
+    for (const user of requesters) {
+      await user.program.methods.requestRedeem(sharesMint.parseAmount(10)).accounts(cmmAccounts).rpc()
+    }
+    await closeEpoch(0)
+    const filledShares = sharesMint.parseAmountBigInt(30) - sharesMint.balanceOfTokenAccount(ADDRESSES.REDEEM_ESCROW)
+    expect(filledShares % 3n).to.not.equal(0n)
+
+    let claimed = 0n
+    for (const user of requesters) {
+      const before = assetMint.balanceOf(user.publicKey)
+      await user.program.methods
+        .claimRedeem()
+        .accounts({ ...cmmAccounts, withdrawalEpoch: ADDRESSES.WITHDRAWAL_EPOCH(0) })
+        .rpc()
+      claimed += assetMint.balanceOf(user.publicKey) - before
+    }
+    expect(claimed).to.equal(assetMint.parseAmountBigInt(10) + 1n)
+
+    // The remainders add up to the escrow, so every request can be cancelled in full
+    for (const user of requesters) {
+      await user.program.methods.cancelRedeemRequest().accounts(cmmAccounts).rpc()
+    }
+    expect(sharesMint.balanceOfTokenAccount(ADDRESSES.REDEEM_ESCROW)).to.equal(0n)
+    const remainders = requesters.map((user) => sharesMint.balanceOf(user.publicKey))
+    expect(remainders.reduce((a, b) => a + b)).to.equal(sharesMint.parseAmountBigInt(30) - filledShares)
+  })
+
+  it('rejects claim before the epoch is closed', async () => {
+    const { provider, users, assetMint, sharesMint, cmmAccounts } = fixture
+    const user = users[0]
+    await user.program.methods.deposit(assetMint.parseAmount(10)).accounts(user.accounts).rpc()
+    nextSlot(provider)
+    await user.program.methods.requestRedeem(sharesMint.parseAmount(4)).accounts(cmmAccounts).rpc()
+
+    await expect(
+      user.program.methods.claimRedeem().accounts({ ...cmmAccounts, withdrawalEpoch: null }).rpc()
+    ).to.be.rejectedWith('NoClaimableRedeemRequest')
+  })
+
+  it('closes the epoch through fulfillRedeem', async () => {
+    const { provider, program, admin, users, assetMint, sharesMint, cmmAccounts } = fixture
+    const [alice, bob] = users
+    await alice.program.methods.deposit(assetMint.parseAmount(10)).accounts(alice.accounts).rpc()
+    await bob.program.methods.deposit(assetMint.parseAmount(30)).accounts(bob.accounts).rpc()
+    nextSlot(provider)
+    await alice.program.methods.requestRedeem(sharesMint.parseAmount(4)).accounts(cmmAccounts).rpc()
+    await bob.program.methods.requestRedeem(sharesMint.parseAmount(6)).accounts(cmmAccounts).rpc()
+
+    const accounts = {
+      ...cmmAccounts,
+      access: ADDRESSES.ACCESS(admin.publicKey),
+      withdrawalEpoch: ADDRESSES.WITHDRAWAL_EPOCH(0),
+    }
+    await admin.program.methods.fulfillRedeem().accounts(accounts).rpc()
+    for (const [user, amount] of [
+      [alice, 4],
+      [bob, 6],
+    ] as const) {
+      const viewAccounts = {
+        user: user.publicKey,
+        redeemRequest: ADDRESSES.REDEEM_REQUEST(user.publicKey),
+        withdrawalEpoch: ADDRESSES.WITHDRAWAL_EPOCH(0),
+      }
+      expect((await program.methods.claimableRedeemRequest().accounts(viewAccounts).view()).toString()).to.equal(
+        sharesMint.parseAmount(amount).toString()
+      )
+    }
+
+    await expect(
+      admin.program.methods
+        .fulfillRedeem()
+        .accounts({ ...accounts, withdrawalEpoch: ADDRESSES.WITHDRAWAL_EPOCH(1) })
+        .rpc()
+    ).to.be.rejectedWith('NoPendingRedeemRequest')
+  })
+
+  it('rejects closing an epoch without KEEPER permission', async () => {
+    const { provider, users, assetMint, sharesMint, cmmAccounts } = fixture
+    const user = users[0]
+    await user.program.methods.deposit(assetMint.parseAmount(10)).accounts(user.accounts).rpc()
+    nextSlot(provider)
+    await user.program.methods.requestRedeem(sharesMint.parseAmount(4)).accounts(cmmAccounts).rpc()
+
+    await expect(
+      users[1].program.methods
+        .closeWithdrawalEpoch()
+        .accounts({ ...cmmAccounts, access: null, withdrawalEpoch: ADDRESSES.WITHDRAWAL_EPOCH(0) })
+        .rpc()
+    ).to.be.rejectedWith('UnauthorizedPermission')
+  })
+})
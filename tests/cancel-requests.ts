@@ -0,0 +1,60 @@
+import { expect } from 'chai'
+import { createFixture, Fixture } from './fixtures/tokenized-vault-fixture'
+import { nextSlot } from './fixtures/utils'
+import { ADDRESSES, PERMISSIONS } from '../shared/tokenized-vault.constants'
+
+describe('cancel requests', () => {
+  let fixture: Fixture
+
+  beforeEach(async () => {
+    fixture = await createFixture()
+    const { program, admin } = fixture
+    await program.methods.grantPermission(PERMISSIONS.KEEPER).accounts({ to: admin.publicKey }).rpc()
+  })
+
+  it('returns pooled assets when a deposit request is cancelled', async () => {
+    const { provider, program, users, assetMint, cmmAccounts } = fixture
+    const user = users[0]
+    await program.methods.setAsyncDeposits(true).rpc()
+    await user.program.methods.requestDeposit(assetMint.parseAmount(10)).accounts(cmmAccounts).rpc()
+
+    await expect(
+      user.program.methods.cancelDepositRequest().accounts(cmmAccounts).rpc()
+    ).splBalancesHaveChanged(provider, assetMint.publicKey, [
+      { wallet: user.publicKey, amount: assetMint.parseAmount(10) },
+    ])
+    expect(provider.client.getAccount(ADDRESSES.DEPOSIT_REQUEST(user.publicKey))).to.be.null
+  })
+
+  it('returns escrowed shares when a redeem request is cancelled', async () => {
+    const { provider, users, assetMint, sharesMint, cmmAccounts } = fixture
+    const user = users[0]
+    await user.program.methods.deposit(assetMint.parseAmount(10)).accounts(user.accounts).rpc()
+    nextSlot(provider)
+    await user.program.methods.requestRedeem(sharesMint.parseAmount(4)).accounts(cmmAccounts).rpc()
+
+    await expect(
+      user.program.methods.cancelRedeemRequest().accounts(cmmAccounts).rpc()
+    ).splBalancesHaveChanged(provider, sharesMint.publicKey, [
+      { wallet: user.publicKey, amount: sharesMint.parseAmount(4) },
+    ])
+    expect(provider.client.getAccount(ADDRESSES.REDEEM_REQUEST(user.publicKey))).to.be.null
+  })
+
+  it('rejects cancellation while locked for settlement', async () => {
+    const { provider, admin, users, assetMint, sharesMint, cmmAccounts } = fixture
+    const user = users[0]
+    await user.program.methods.deposit(assetMint.parseAmount(10)).accounts(user.accounts).rpc()
+    nextSlot(provider)
+    await user.program.methods.requestRedeem(sharesMint.parseAmount(4)).accounts(cmmAccounts).rpc()
+
+    const accounts = { access: ADDRESSES.ACCESS(admin.publicKey) }
+    await admin.program.methods.setCancellationsLocked(true).accounts(accounts).rpc()
+    await expect(user.program.methods.cancelRedeemRequest().accounts(cmmAccounts).rpc()).to.be.rejectedWith(
+      'CancellationsLocked'
+    )
+
+    await admin.program.methods.setCancellationsLocked(false).accounts(accounts).rpc()
+    await user.program.methods.cancelRedeemRequest().accounts(cmmAccounts).rpc()
+  })
+})
@@ -0,0 +1,47 @@
+import { expect } from 'chai'
+import { ExtensionType, getExtensionData, getExtensionTypes, unpackMint } from '@solana/spl-token'
+import { PublicKey } from '@solana/web3.js'
+import { createFixture, Fixture } from './fixtures/tokenized-vault-fixture'
+import { nextSlot } from './fixtures/utils'
+import { INIT_OPTIONS } from '../shared/tokenized-vault.constants'
+
+describe('confidential shares', () => {
+  let fixture: Fixture
+
+  beforeEach(async () => {
+    fixture = await createFixture({ initOptions: INIT_OPTIONS.SHARES_CONFIDENTIAL_TRANSFERS })
+  })
+
+  const sharesMintData = () => {
+    const { client, sharesMint } = fixture
+    const account = client.getAccount(sharesMint.publicKey) as any
+    return unpackMint(sharesMint.publicKey, account, sharesMint.tokenProgramId)
+  }
+
+  it('initializes the shares mint with confidential transfers', async () => {
+    const { sharesMint } = fixture
+    const mint = sharesMintData()
+    expect(getExtensionTypes(mint.tlvData)).to.include(ExtensionType.ConfidentialTransferMint)
+
+    // authority (32) | auto_approve_new_accounts (1) | auditor_elgamal_pubkey (32)
+    const data = getExtensionData(ExtensionType.ConfidentialTransferMint, mint.tlvData)!
+    expect(new PublicKey(data.subarray(0, 32)).equals(sharesMint.publicKey)).to.be.true
+    expect(data[32]).to.equal(1)
+    expect(data.subarray(33, 65).every((b) => b === 0)).to.be.true
+  })
+
+  it('is not enabled by default', async () => {
+    fixture = await createFixture()
+    expect(getExtensionTypes(sharesMintData().tlvData)).to.not.include(ExtensionType.ConfidentialTransferMint)
+  })
+
+  it('redeems from the public balance', async () => {
+    const { provider, users, assetMint, sharesMint } = fixture
+    const user = users[0]
+    await user.program.methods.deposit(assetMint.parseAmount(10)).accounts(user.accounts).rpc()
+    nextSlot(provider)
+
+    await user.program.methods.redeem(sharesMint.parseAmount(10)).accounts(user.accounts).rpc()
+    expect(sharesMint.balanceOf(user.publicKey)).to.equal(0n)
+  })
+})
@@ -0,0 +1,75 @@
+import { expect } from 'chai'
+import BN from 'bn.js'
+import { createFixture, Fixture, NOW } from './fixtures/tokenized-vault-fixture'
+import { jumpToTimestamp, nextSlot } from './fixtures/utils'
+import { ADDRESSES, INIT_OPTIONS, PERMISSIONS } from '../shared/tokenized-vault.constants'
+import { DAY_SECONDS } from '../shared/utils'
+
+describe('cooldown', () => {
+  let fixture: Fixture
+
+  beforeEach(async () => {
+    fixture = await createFixture({ initOptions: INIT_OPTIONS.SHARES_NON_TRANSFERABLE })
+    const { program, admin } = fixture
+    await program.methods.grantPermission(PERMISSIONS.LIMIT_MANAGER).accounts({ to: admin.publicKey }).rpc()
+    await admin.program.methods
+      .setWithdrawCooldown(new BN(DAY_SECONDS))
+      .accounts({ access: ADDRESSES.ACCESS(admin.publicKey) })
+      .rpc()
+  })
+
+  it('rejects withdraw and redeem until the cooldown elapses', async () => {
+    const { provider, users, assetMint, sharesMint } = fixture
+    const user = users[0]
+    await user.program.methods.deposit(assetMint.parseAmount(10)).accounts(user.accounts).rpc()
+
+    await expect(
+      user.program.methods.withdraw(assetMint.parseAmount(1)).accounts(user.accounts).rpc()
+    ).to.be.rejectedWith('CooldownActive')
+    await expect(
+      user.program.methods.redeem(sharesMint.parseAmount(1)).accounts(user.accounts).rpc()
+    ).to.be.rejectedWith('CooldownActive')
+
+    jumpToTimestamp(provider, NOW.getTime() / 1000 + DAY_SECONDS)
+    nextSlot(provider)
+    await expect(
+      user.program.methods.redeem(sharesMint.parseAmount(1)).accounts(user.accounts).rpc()
+    ).splBalancesHaveChanged(provider, assetMint.publicKey, [
+      { wallet: user.publicKey, amount: assetMint.parseAmount(1) },
+    ])
+  })
+
+  it('max_withdraw and max_redeem return 0 during the cooldown', async () => {
+    const { provider, program, users, assetMint, sharesMint, cmmAccounts } = fixture
+    const user = users[0]
+    await user.program.methods.deposit(assetMint.parseAmount(10)).accounts(user.accounts).rpc()
+
+    const accounts = {
+      ...cmmAccounts,
+      user: user.publicKey,
+      userPosition: ADDRESSES.USER_POSITION(user.publicKey),
+      userSharesVault: sharesMint.getAtaOf(user.publicKey),
+    }
+    expect((await program.methods.maxWithdraw().accounts(accounts).view()).toString()).to.equal('0')
+    expect((await program.methods.maxRedeem().accounts(accounts).view()).toString()).to.equal('0')
+
+    jumpToTimestamp(provider, NOW.getTime() / 1000 + DAY_SECONDS)
+    nextSlot(provider)
+    expect((await program.methods.maxWithdraw().accounts(accounts).view()).toString()).to.equal(
+      assetMint.parseAmount(10).toString()
+    )
+    expect((await program.methods.maxRedeem().accounts(accounts).view()).toString()).to.equal(
+      sharesMint.parseAmount(10).toString()
+    )
+  })
+
+  it('rejects a cooldown on vaults with transferable shares', async () => {
+    const { program, admin } = await createFixture()
+    await program.methods.grantPermission(PERMISSIONS.LIMIT_MANAGER).accounts({ to: admin.publicKey }).rpc()
+    const access = ADDRESSES.ACCESS(admin.publicKey)
+    await expect(
+      admin.program.methods.setWithdrawCooldown(new BN(DAY_SECONDS)).accounts({ access }).rpc()
+    ).to.be.rejectedWith('CooldownRequiresNonTransferableShares')
+    await admin.program.methods.setWithdrawCooldown(new BN(0)).accounts({ access }).rpc()
+  })
+})
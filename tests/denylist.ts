@@ -0,0 +1,78 @@
+import { expect } from 'chai'
+import { createFixture, Fixture } from './fixtures/tokenized-vault-fixture'
+import { nextSlot } from './fixtures/utils'
+import { ADDRESSES, PERMISSIONS } from '../shared/tokenized-vault.constants'
+
+describe('denylist', () => {
+  let fixture: Fixture
+
+  beforeEach(async () => {
+    fixture = await createFixture()
+    const { program, admin } = fixture
+    await program.methods.grantPermission(PERMISSIONS.COMPLIANCE).accounts({ to: admin.publicKey }).rpc()
+  })
+
+  it('blocks deposit, mint, withdraw and redeem for a blocked address', async () => {
+    const { admin, users, assetMint, sharesMint } = fixture
+    const user = users[0]
+    await user.program.methods.deposit(assetMint.parseAmount(10)).accounts(user.accounts).rpc()
+
+    await admin.program.methods
+      .blockAddress()
+      .accounts({ access: ADDRESSES.ACCESS(admin.publicKey), target: user.publicKey })
+      .rpc()
+
+    await expect(
+      user.program.methods.deposit(assetMint.parseAmount(1)).accounts(user.accounts).rpc()
+    ).to.be.rejectedWith('AddressBlocked')
+    await expect(
+      user.program.methods.mint(sharesMint.parseAmount(1)).accounts(user.accounts).rpc()
+    ).to.be.rejectedWith('AddressBlocked')
+    await expect(
+      user.program.methods.withdraw(assetMint.parseAmount(1)).accounts(user.accounts).rpc()
+    ).to.be.rejectedWith('AddressBlocked')
+    await expect(
+      user.program.methods.redeem(sharesMint.parseAmount(1)).accounts(user.accounts).rpc()
+    ).to.be.rejectedWith('AddressBlocked')
+
+    // Other users are unaffected
+    await users[1].program.methods.deposit(assetMint.parseAmount(1)).accounts(users[1].accounts).rpc()
+  })
+
+  it('blocks withdrawals to a blocked receiver', async () => {
+    const { admin, provider, users, assetMint, sharesMint } = fixture
+    const [user, receiver] = users
+    await user.program.methods.deposit(assetMint.parseAmount(10)).accounts(user.accounts).rpc()
+    nextSlot(provider)
+    await admin.program.methods
+      .blockAddress()
+      .accounts({ access: ADDRESSES.ACCESS(admin.publicKey), target: receiver.publicKey })
+      .rpc()
+
+    const accounts = { ...user.accounts, ...receiver.accounts }
+    await expect(
+      user.program.methods.withdraw(assetMint.parseAmount(1)).accounts(accounts).rpc()
+    ).to.be.rejectedWith('AddressBlocked')
+    await expect(
+      user.program.methods.redeem(sharesMint.parseAmount(1)).accounts(accounts).rpc()
+    ).to.be.rejectedWith('AddressBlocked')
+  })
+
+  it('restores access once unblocked', async () => {
+    const { admin, provider, users, assetMint } = fixture
+    const user = users[0]
+    const accounts = { access: ADDRESSES.ACCESS(admin.publicKey), target: user.publicKey }
+    await admin.program.methods.blockAddress().accounts(accounts).rpc()
+    await admin.program.methods.unblockAddress().accounts(accounts).rpc()
+
+    expect(provider.client.getAccount(ADDRESSES.BLOCKED(user.publicKey))).to.be.null
+    await user.program.methods.deposit(assetMint.parseAmount(1)).accounts(user.accounts).rpc()
+  })
+
+  it('rejects blocking without COMPLIANCE permission', async () => {
+    const { users } = fixture
+    await expect(
+      users[0].program.methods.blockAddress().accounts({ access: null, target: users[1].publicKey }).rpc()
+    ).to.be.rejectedWith('UnauthorizedPermission')
+  })
+})
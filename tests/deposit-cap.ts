@@ -0,0 +1,100 @@
+import { expect } from 'chai'
+import BN from 'bn.js'
+import { createFixture } from './fixtures/tokenized-vault-fixture'
+import { ADDRESSES, PERMISSIONS } from '../shared/tokenized-vault.constants'
+
+describe('deposit-cap', () => {
+  it('max_deposit and max_mint are unlimited by default', async () => {
+    const { program, users, cmmAccounts } = await createFixture()
+    const accounts = { ...cmmAccounts, user: users[0].publicKey, userPosition: null }
+    const maxDeposit = await program.methods.maxDeposit().accounts(accounts).view()
+    const maxMint = await program.methods.maxMint().accounts(accounts).view()
+    expect(maxDeposit.toString()).to.equal('18446744073709551615')
+    expect(maxMint.toString()).to.equal('18446744073709551615')
+  })
+
+  it('rejects set_deposit_cap without LIMIT_MANAGER permission', async () => {
+    const { users } = await createFixture()
+    const user = users[0]
+    await expect(
+      user.program.methods.setDepositCap(new BN(1)).accounts({ access: null }).rpc()
+    ).to.be.rejectedWith('UnauthorizedPermission')
+  })
+
+  it('limits deposit and mint to the remaining headroom', async () => {
+    const { program, users, admin, assetMint, sharesMint, cmmAccounts } = await createFixture()
+    const user = users[0]
+
+    await program.methods
+      .grantPermission(PERMISSIONS.LIMIT_MANAGER)
+      .accounts({ to: admin.publicKey })
+      .rpc()
+    await admin.program.methods
+      .setDepositCap(assetMint.parseAmount(1000))
+      .accounts({ access: ADDRESSES.ACCESS(admin.publicKey) })
+      .rpc()
+
+    await user.program.methods.deposit(assetMint.parseAmount(600)).accounts(user.accounts).rpc()
+
+    const accounts = { ...cmmAccounts, user: user.publicKey, userPosition: ADDRESSES.USER_POSITION(user.publicKey) }
+    const maxDeposit = await program.methods.maxDeposit().accounts(accounts).view()
+    expect(maxDeposit.toString()).to.equal(assetMint.parseAmount(400).toString())
+    const maxMint = await program.methods.maxMint().accounts(accounts).view()
+    expect(maxMint.toString()).to.equal(sharesMint.parseAmount(400).toString())
+
+    await expect(
+      user.program.methods.deposit(assetMint.parseAmount(401)).accounts(user.accounts).rpc()
+    ).to.be.rejectedWith('DepositCapExceeded')
+    await expect(
+      user.program.methods.mint(sharesMint.parseAmount(401)).accounts(user.accounts).rpc()
+    ).to.be.rejectedWith('DepositCapExceeded')
+    await user.program.methods.deposit(assetMint.parseAmount(400)).accounts(user.accounts).rpc()
+  })
+
+  it('pauses deposits with a zero cap', async () => {
+    const { program, users, admin, assetMint, cmmAccounts } = await createFixture()
+    const user = users[0]
+
+    await program.methods
+      .grantPermission(PERMISSIONS.LIMIT_MANAGER)
+      .accounts({ to: admin.publicKey })
+      .rpc()
+    await admin.program.methods
+      .setDepositCap(new BN(0))
+      .accounts({ access: ADDRESSES.ACCESS(admin.publicKey) })
+      .rpc()
+
+    const accounts = { ...cmmAccounts, user: user.publicKey, userPosition: null }
+    const maxDeposit = await program.methods.maxDeposit().accounts(accounts).view()
+    expect(maxDeposit.toString()).to.equal('0')
+    await expect(
+      user.program.methods.deposit(assetMint.parseAmount(1)).accounts(user.accounts).rpc()
+    ).to.be.rejectedWith('DepositCapExceeded')
+  })
+
+  it('leaves configs created before the cap uncapped', async () => {
+    const { program, client, users, admin, assetMint, cmmAccounts } = await createFixture()
+    const user = users[0]
+    // Zero `limits_initialized`, `deposit_cap` and `user_deposit_limit` as in the padding of old configs
+    const account = client.getAccount(ADDRESSES.CONFIG)!
+    const data = Buffer.from(account.data)
+    data.fill(0, 79, 96)
+    client.setAccount(ADDRESSES.CONFIG, { ...account, data })
+
+    const accounts = { ...cmmAccounts, user: user.publicKey, userPosition: null }
+    const maxDeposit = await program.methods.maxDeposit().accounts(accounts).view()
+    expect(maxDeposit.toString()).to.equal('18446744073709551615')
+    await user.program.methods.deposit(assetMint.parseAmount(1000)).accounts(user.accounts).rpc()
+
+    // Setting a cap keeps the default limit of the other users
+    await program.methods
+      .grantPermission(PERMISSIONS.LIMIT_MANAGER)
+      .accounts({ to: admin.publicKey })
+      .rpc()
+    await admin.program.methods
+      .setDepositCap(assetMint.parseAmount(2000))
+      .accounts({ access: ADDRESSES.ACCESS(admin.publicKey) })
+      .rpc()
+    await users[1].program.methods.deposit(assetMint.parseAmount(1000)).accounts(users[1].accounts).rpc()
+  })
+})
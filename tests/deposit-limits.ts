@@ -0,0 +1,103 @@
+import { expect } from 'chai'
+import BN from 'bn.js'
+import { createFixture, Fixture } from './fixtures/tokenized-vault-fixture'
+import { ADDRESSES, PERMISSIONS } from '../shared/tokenized-vault.constants'
+import { nextSlot } from './fixtures/utils'
+
+describe('deposit-limits', () => {
+  let fixture: Fixture
+
+  beforeEach(async () => {
+    fixture = await createFixture()
+    const { program, admin, assetMint } = fixture
+    await program.methods.grantPermission(PERMISSIONS.LIMIT_MANAGER).accounts({ to: admin.publicKey }).rpc()
+    await admin.program.methods
+      .setDefaultDepositLimit(assetMint.parseAmount(100))
+      .accounts({ access: ADDRESSES.ACCESS(admin.publicKey) })
+      .rpc()
+  })
+
+  it('enforces the default per-user limit on net deposits', async () => {
+    const { provider, users, assetMint } = fixture
+    const user = users[0]
+    await user.program.methods.deposit(assetMint.parseAmount(100)).accounts(user.accounts).rpc()
+    await expect(
+      user.program.methods.deposit(assetMint.parseAmount(1)).accounts(user.accounts).rpc()
+    ).to.be.rejectedWith('UserDepositLimitExceeded')
+
+    // Withdrawals free up headroom
+    nextSlot(provider)
+    await user.program.methods.withdraw(assetMint.parseAmount(10)).accounts(user.accounts).rpc()
+    await user.program.methods.deposit(assetMint.parseAmount(10)).accounts(user.accounts).rpc()
+
+    // Other users are tracked independently
+    await users[1].program.methods.deposit(assetMint.parseAmount(100)).accounts(users[1].accounts).rpc()
+  })
+
+  it('applies per-user overrides', async () => {
+    const { users, admin, assetMint, cmmAccounts, program } = fixture
+    const institution = users[0]
+    await admin.program.methods
+      .setUserDepositLimit(assetMint.parseAmount(1000))
+      .accounts({ access: ADDRESSES.ACCESS(admin.publicKey), user: institution.publicKey })
+      .rpc()
+
+    await institution.program.methods.deposit(assetMint.parseAmount(1000)).accounts(institution.accounts).rpc()
+    const maxDeposit = await program.methods
+      .maxDeposit()
+      .accounts({
+        ...cmmAccounts,
+        user: institution.publicKey,
+        userPosition: ADDRESSES.USER_POSITION(institution.publicKey),
+      })
+      .view()
+    expect(maxDeposit.toString()).to.equal('0')
+
+    // Clearing the override falls back to the default limit
+    await admin.program.methods
+      .setUserDepositLimit(null)
+      .accounts({ access: ADDRESSES.ACCESS(admin.publicKey), user: institution.publicKey })
+      .rpc()
+    await expect(
+      institution.program.methods.deposit(assetMint.parseAmount(1)).accounts(institution.accounts).rpc()
+    ).to.be.rejectedWith('UserDepositLimitExceeded')
+  })
+
+  it('blocks deposits of users without an override with a zero default limit', async () => {
+    const { admin, users, assetMint } = fixture
+    await admin.program.methods
+      .setDefaultDepositLimit(new BN(0))
+      .accounts({ access: ADDRESSES.ACCESS(admin.publicKey) })
+      .rpc()
+
+    await expect(
+      users[0].program.methods.deposit(assetMint.parseAmount(1)).accounts(users[0].accounts).rpc()
+    ).to.be.rejectedWith('UserDepositLimitExceeded')
+  })
+
+  it('leaves configs created before the limits unlimited', async () => {
+    const { client, admin, users, assetMint } = fixture
+    // Zero `limits_initialized`, `deposit_cap` and `user_deposit_limit` as in the padding of old configs
+    const account = client.getAccount(ADDRESSES.CONFIG)!
+    const data = Buffer.from(account.data)
+    data.fill(0, 79, 96)
+    client.setAccount(ADDRESSES.CONFIG, { ...account, data })
+    await users[0].program.methods.deposit(assetMint.parseAmount(1000)).accounts(users[0].accounts).rpc()
+
+    await admin.program.methods
+      .setDefaultDepositLimit(assetMint.parseAmount(100))
+      .accounts({ access: ADDRESSES.ACCESS(admin.publicKey) })
+      .rpc()
+    await expect(
+      users[1].program.methods.deposit(assetMint.parseAmount(101)).accounts(users[1].accounts).rpc()
+    ).to.be.rejectedWith('UserDepositLimitExceeded')
+    await users[1].program.methods.deposit(assetMint.parseAmount(100)).accounts(users[1].accounts).rpc()
+  })
+
+  it('rejects overrides without LIMIT_MANAGER permission', async () => {
+    const { users } = fixture
+    await expect(
+      users[0].program.methods.setUserDepositLimit(null).accounts({ access: null, user: users[1].publicKey }).rpc()
+    ).to.be.rejectedWith('UnauthorizedPermission')
+  })
+})
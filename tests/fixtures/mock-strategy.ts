@@ -0,0 +1,71 @@
+import { Program } from '@coral-xyz/anchor'
+import { utf8 } from '@coral-xyz/anchor/dist/cjs/utils/bytes'
+import { PublicKey } from '@solana/web3.js'
+import BN from 'bn.js'
+import { MockStrategy } from '../../target/types/mock_strategy'
+import IDL from '../../target/idl/mock_strategy.json'
+import { ADDRESSES } from '../../shared/tokenized-vault.constants'
+import { Fixture } from './tokenized-vault-fixture'
+
+// Creates the mock strategy's asset account for the vault; registering the strategy is left to the tests
+export const createMockStrategy = async (fixture: Fixture) => {
+  const { provider, admin, assetMint } = fixture
+  const program = new Program<MockStrategy>(IDL, provider)
+  const strategy = ADDRESSES.STRATEGY(program.programId)
+  const [authority] = PublicKey.findProgramAddressSync(
+    [utf8.encode('authority'), strategy.toBuffer()],
+    program.programId
+  )
+  const assetAccount = await assetMint.createTokenAccount(authority)
+
+  // Accounts of the vault's strategy instructions, signed by the admin
+  const accounts = {
+    access: ADDRESSES.ACCESS(admin.publicKey),
+    strategyProgram: program.programId,
+    assetMint: assetMint.publicKey,
+    strategyAssetAccount: assetAccount,
+    assetTokenProgram: assetMint.tokenProgramId,
+  }
+  // Accounts forwarded by the vault after the strategy interface accounts
+  const remainingAccounts = [{ pubkey: authority, isSigner: false, isWritable: false }]
+  const simulationAccounts = {
+    strategy,
+    strategyAssetAccount: assetAccount,
+    assetMint: assetMint.publicKey,
+    assetTokenProgram: assetMint.tokenProgramId,
+  }
+
+  return {
+    program,
+    programId: program.programId,
+    strategy,
+    authority,
+    assetAccount,
+    accounts,
+    remainingAccounts,
+    balance: () => assetMint.balanceOfTokenAccount(assetAccount),
+    add: (maxDebt: BN) => admin.program.methods.addStrategy(maxDebt).accounts(accounts).rpc(),
+    updateDebt: (targetDebt: BN) =>
+      admin.program.methods.updateDebt(targetDebt).accounts(accounts).remainingAccounts(remainingAccounts).rpc(),
+    remove: () =>
+      admin.program.methods
+        .removeStrategy()
+        .accounts({ access: accounts.access, strategyProgram: program.programId })
+        .rpc(),
+    report: (feeRecipientSharesVault: PublicKey | null = null) =>
+      admin.program.methods
+        .report()
+        .accounts({ ...accounts, feeRecipientSharesVault })
+        .remainingAccounts(remainingAccounts)
+        .rpc(),
+    // Funded by the deployer, who holds the asset supply
+    simulateGain: (amount: BN) =>
+      program.methods
+        .simulateGain(amount)
+        .accounts({ ...simulationAccounts, funderAssetAccount: assetMint.getAtaOf(provider.wallet.publicKey) })
+        .rpc(),
+    simulateLoss: (amount: BN) => program.methods.simulateLoss(amount).accounts(simulationAccounts).rpc(),
+  }
+}
+
+export type MockStrategyFixture = Awaited<ReturnType<typeof createMockStrategy>>
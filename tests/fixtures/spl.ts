@@ -5,24 +5,48 @@ import {
   MULTISIG_SIZE,
   createInitializeMultisigInstruction,
   createInitializeMint2Instruction,
+  createInitializeAccount3Instruction,
+  createInitializeTransferFeeConfigInstruction,
+  createInitializePermanentDelegateInstruction,
+  createInitializeNonTransferableMintInstruction,
+  createInitializeTransferHookInstruction,
+  ExtensionType,
+  getMintLen,
   getAssociatedTokenAddressSync,
   unpackAccount,
   createAssociatedTokenAccountIdempotentInstruction,
   createMintToInstruction,
   createTransferCheckedInstruction,
   unpackMint,
+  getExtensionData,
+  getAccountLenForMint,
 } from '@solana/spl-token'
+import { unpack as unpackTokenMetadata } from '@solana/spl-token-metadata'
 import { LiteSVMProvider } from 'anchor-litesvm'
 import BN from 'bn.js'
 import { Keypair, SystemProgram, Transaction, PublicKey } from '@solana/web3.js'
 import { toBN } from '../../shared/utils'
 
-export const createTokenAndMint = async (
-  provider: LiteSVMProvider,
-  supply: bigint,
-  tokenProgramId = TOKEN_2022_PROGRAM_ID
-) => {
+interface MintOptions {
+  tokenProgramId?: PublicKey
+  transferFeeBps?: number
+  freezeAuthority?: PublicKey
+  permanentDelegate?: PublicKey
+  extensions?: ExtensionType[]
+}
+
+export const createTokenAndMint = async (provider: LiteSVMProvider, supply: bigint, options: MintOptions = {}) => {
   const decimals = 6
+  const {
+    tokenProgramId = TOKEN_2022_PROGRAM_ID,
+    transferFeeBps = 0,
+    freezeAuthority = null,
+    permanentDelegate,
+  } = options
+  const extensions = [...(options.extensions ?? [])]
+  if (transferFeeBps > 0) extensions.push(ExtensionType.TransferFeeConfig)
+  if (permanentDelegate) extensions.push(ExtensionType.PermanentDelegate)
+  const mintSize = extensions.length > 0 ? getMintLen(extensions) : MINT_SIZE
 
   const { client, wallet } = provider
   const mintKp = Keypair.generate()
@@ -33,8 +57,8 @@ export const createTokenAndMint = async (
     SystemProgram.createAccount({
       fromPubkey: wallet.publicKey,
       newAccountPubkey: mint,
-      space: MINT_SIZE,
-      lamports: Number(provider.client.minimumBalanceForRentExemption(BigInt(MINT_SIZE))),
+      space: mintSize,
+      lamports: Number(provider.client.minimumBalanceForRentExemption(BigInt(mintSize))),
       programId: tokenProgramId,
     }),
     SystemProgram.createAccount({
@@ -44,8 +68,33 @@ export const createTokenAndMint = async (
       lamports: Number(provider.client.minimumBalanceForRentExemption(BigInt(MULTISIG_SIZE))),
       programId: tokenProgramId,
     }),
-    createInitializeMultisigInstruction(multisigKp.publicKey, [provider.wallet.payer], 1, tokenProgramId),
-    createInitializeMint2Instruction(mint, decimals, multisigKp.publicKey, null, tokenProgramId)
+    createInitializeMultisigInstruction(multisigKp.publicKey, [provider.wallet.payer], 1, tokenProgramId)
+  )
+  if (transferFeeBps > 0) {
+    transaction.add(
+      createInitializeTransferFeeConfigInstruction(
+        mint,
+        wallet.publicKey,
+        wallet.publicKey,
+        transferFeeBps,
+        BigInt('18446744073709551615'),
+        tokenProgramId
+      )
+    )
+  }
+  if (permanentDelegate) {
+    transaction.add(createInitializePermanentDelegateInstruction(mint, permanentDelegate, tokenProgramId))
+  }
+  if (options.extensions?.includes(ExtensionType.NonTransferable)) {
+    transaction.add(createInitializeNonTransferableMintInstruction(mint, tokenProgramId))
+  }
+  if (options.extensions?.includes(ExtensionType.TransferHook)) {
+    transaction.add(
+      createInitializeTransferHookInstruction(mint, wallet.publicKey, Keypair.generate().publicKey, tokenProgramId)
+    )
+  }
+  transaction.add(
+    createInitializeMint2Instruction(mint, decimals, multisigKp.publicKey, freezeAuthority, tokenProgramId)
   )
   await provider.send(transaction, [mintKp, multisigKp])
 
@@ -94,6 +143,24 @@ export const wrapMint = (provider: LiteSVMProvider, mint: PublicKey) => {
     await provider.send(tx)
   }
 
+  // Creates a token account at a fresh address (not an ATA) owned by `owner`
+  const createTokenAccount = async (owner: PublicKey) => {
+    const accountKp = Keypair.generate()
+    const accountLen = getAccountLenForMint(mintData)
+    const tx = new Transaction().add(
+      SystemProgram.createAccount({
+        fromPubkey: wallet.publicKey,
+        newAccountPubkey: accountKp.publicKey,
+        space: accountLen,
+        lamports: Number(provider.client.minimumBalanceForRentExemption(BigInt(accountLen))),
+        programId: tokenProgramId,
+      }),
+      createInitializeAccount3Instruction(accountKp.publicKey, mint, owner, tokenProgramId)
+    )
+    await provider.send(tx, [accountKp])
+    return accountKp.publicKey
+  }
+
   const mintTokenTo = async (owner: PublicKey, amount: bigint | BN) => {
     await createAtaOf(owner)
     const ix = createMintToInstruction(
@@ -155,11 +222,19 @@ export const wrapMint = (provider: LiteSVMProvider, mint: PublicKey) => {
     return mintData.supply
   }
 
+  const tokenMetadata = () => {
+    const mintAcc = provider.client.getAccount(mint) as any
+    const mintData = unpackMint(mint, mintAcc, tokenProgramId)
+    const data = getExtensionData(ExtensionType.TokenMetadata, mintData.tlvData)
+    return data ? unpackTokenMetadata(data) : null
+  }
+
   return {
     publicKey: mint,
     getAtaOf,
     mintTokenTo,
     createAtaOf,
+    createTokenAccount,
     transferTo,
     balanceOf,
     balanceOfTokenAccount,
@@ -169,6 +244,7 @@ export const wrapMint = (provider: LiteSVMProvider, mint: PublicKey) => {
     parseAmount,
     parseAmountBigInt,
     supply,
+    tokenMetadata,
   }
 }
 
@@ -1,16 +1,20 @@
 import { fromWorkspace, LiteSVMProvider } from 'anchor-litesvm'
 import { Program, Wallet } from '@coral-xyz/anchor'
-import { Keypair } from '@solana/web3.js'
+import { Keypair, PublicKey } from '@solana/web3.js'
 import deepmerge from 'deepmerge'
 import { TokenizedVault } from '../../target/types/tokenized_vault'
 import IDL from '../../target/idl/tokenized_vault.json'
-import { ADDRESSES, PERMISSIONS } from '../../shared/tokenized-vault.constants'
+import { SharesTransferHook } from '../../target/types/shares_transfer_hook'
+import HOOK_IDL from '../../target/idl/shares_transfer_hook.json'
+import { ADDRESSES, INIT_OPTIONS, PERMISSIONS } from '../../shared/tokenized-vault.constants'
 import { createTokenAndMint, MintWrapper, wrapMint } from './spl'
 import { parseUnits } from 'viem'
 
 interface FixtureOptions {
   createConfig?: { createFee: bigint } | false
   createCollateral?: boolean
+  transferFeeBps?: number
+  initOptions?: number
 }
 
 const getDefaultOptions = () => {
@@ -19,6 +23,8 @@ const getDefaultOptions = () => {
       createFee: 100n,
     },
     createCollateral: true,
+    transferFeeBps: 0,
+    initOptions: INIT_OPTIONS.NONE,
   }
 }
 
@@ -29,18 +35,33 @@ export const createFixture = async (options?: FixtureOptions) => {
   const client = fromWorkspace('.')
   const provider = new LiteSVMProvider(client)
   const program = new Program<TokenizedVault>(IDL, provider)
+  const hookProgram = new Program<SharesTransferHook>(HOOK_IDL, provider)
 
   const clock = client.getClock()
   clock.unixTimestamp = BigInt(NOW.getTime()) / 1000n
   client.setClock(clock)
 
-  const assetMint = await createTokenAndMint(provider, parseUnits('100000000000', 6))
+  const assetMint = await createTokenAndMint(provider, parseUnits('100000000000', 6), {
+    transferFeeBps: options.transferFeeBps,
+  })
+
+  let cmmAccounts = {
+    assetTokenProgram: assetMint.tokenProgramId,
+    userAccess: null,
+  }
+  // Deposit/Withdraw accounts of a user, who moves assets through their ATA
+  const accountsOf = (owner: PublicKey) => ({
+    ...cmmAccounts,
+    userAssetVault: assetMint.getAtaOf(owner),
+    receiverBlocked: ADDRESSES.BLOCKED(owner),
+  })
 
   const deployer = {
     keypair: provider.wallet.payer,
     provider,
     program,
     publicKey: provider.wallet.publicKey,
+    accounts: accountsOf(provider.wallet.publicKey),
   }
 
   const users = await Promise.all(
@@ -56,6 +77,7 @@ export const createFixture = async (options?: FixtureOptions) => {
         provider: userProvider,
         program: new Program<TokenizedVault>(IDL, userProvider),
         publicKey: keypair.publicKey,
+        accounts: accountsOf(keypair.publicKey),
       }
     })
   )
@@ -66,28 +88,31 @@ export const createFixture = async (options?: FixtureOptions) => {
 
   if (options.createConfig) {
     await program.methods
-      .initialize({
-        name: 'Test Token',
-        symbol: 'TEST',
-        uri: 'https://test.com',
-      })
+      .initialize(
+        {
+          name: 'Test Token',
+          symbol: 'TEST',
+          uri: 'https://test.com',
+        },
+        options.initOptions
+      )
       .accounts({
         assetMint: assetMint.publicKey,
         assetTokenProgram: assetMint.tokenProgramId,
       })
       .rpc()
+    if (options.initOptions & INIT_OPTIONS.SHARES_TRANSFER_HOOK) {
+      await hookProgram.methods.initializeExtraAccountMetaList().rpc()
+    }
     sharesMint = wrapMint(provider, ADDRESSES.SHARES_MINT)
   }
 
-  let cmmAccounts = {
-    assetTokenProgram: assetMint.tokenProgramId,
-  }
-
   return {
     deployer,
     client,
     provider,
     program,
+    hookProgram,
     users,
     admin,
     assetMint,
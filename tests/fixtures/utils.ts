@@ -9,3 +9,8 @@ export const jumpToTimestamp = (
   clock.unixTimestamp = BigInt(timestamp.toString());
   provider.client.setClock(clock);
 };
+
+export const nextSlot = (provider: LiteSVMProvider) => {
+  const clock = provider.client.getClock();
+  provider.client.warpToSlot(clock.slot + 1n);
+};
@@ -0,0 +1,72 @@
+import { expect } from 'chai'
+import { createFixture, Fixture } from './fixtures/tokenized-vault-fixture'
+import { ADDRESSES, PERMISSIONS } from '../shared/tokenized-vault.constants'
+
+describe('shares metadata', () => {
+  let fixture: Fixture
+
+  beforeEach(async () => {
+    fixture = await createFixture()
+    const { program, admin } = fixture
+    await program.methods.grantPermission(PERMISSIONS.METADATA_MANAGER).accounts({ to: admin.publicKey }).rpc()
+  })
+
+  it('updates name, symbol and uri', async () => {
+    const { admin, sharesMint } = fixture
+    await admin.program.methods
+      .updateSharesMetadata({ name: 'Renamed Vault Shares', symbol: 'RVS', uri: 'https://example.com/vault.json' })
+      .accounts({ access: ADDRESSES.ACCESS(admin.publicKey) })
+      .rpc()
+
+    const metadata = sharesMint.tokenMetadata()!
+    expect(metadata.name).to.equal('Renamed Vault Shares')
+    expect(metadata.symbol).to.equal('RVS')
+    expect(metadata.uri).to.equal('https://example.com/vault.json')
+  })
+
+  it('sets additional fields', async () => {
+    const { admin, sharesMint } = fixture
+    const access = ADDRESSES.ACCESS(admin.publicKey)
+    await admin.program.methods.setMetadataField('risk_rating', 'low').accounts({ access }).rpc()
+    await admin.program.methods.setMetadataField('risk_rating', 'medium').accounts({ access }).rpc()
+
+    expect(sharesMint.tokenMetadata()!.additionalMetadata).to.deep.include(['risk_rating', 'medium'])
+  })
+
+  it('publishes vault parameters at initialization', async () => {
+    const { assetMint, sharesMint } = fixture
+    const fields = sharesMint.tokenMetadata()!.additionalMetadata
+    expect(fields).to.deep.include(['asset_mint', assetMint.publicKey.toBase58()])
+    expect(fields).to.deep.include(['decimals_offset', '3'])
+    expect(fields).to.deep.include(['version', '0.1.0'])
+  })
+
+  it('re-publishes vault parameters on sync', async () => {
+    const { admin, client, sharesMint } = fixture
+    // Change `performance_fee_bps` without republishing, as a program upgrade could
+    const account = client.getAccount(ADDRESSES.CONFIG)!
+    const data = Buffer.from(account.data)
+    data.writeBigUInt64LE(500n, 192)
+    client.setAccount(ADDRESSES.CONFIG, { ...account, data })
+    await admin.program.methods.syncVaultMetadata().accounts({ access: ADDRESSES.ACCESS(admin.publicKey) }).rpc()
+
+    expect(sharesMint.tokenMetadata()!.additionalMetadata).to.deep.include(['performance_fee_bps', '500'])
+  })
+
+  it('rejects the keys of the vault parameters', async () => {
+    const { admin } = fixture
+    const access = ADDRESSES.ACCESS(admin.publicKey)
+    for (const key of ['asset_mint', 'decimals_offset', 'performance_fee_bps', 'version']) {
+      await expect(
+        admin.program.methods.setMetadataField(key, 'spoofed').accounts({ access }).rpc()
+      ).to.be.rejectedWith('ReservedMetadataField')
+    }
+  })
+
+  it('rejects updates without METADATA_MANAGER permission', async () => {
+    const { users } = fixture
+    await expect(
+      users[0].program.methods.setMetadataField('risk_rating', 'low').accounts({ access: null }).rpc()
+    ).to.be.rejectedWith('UnauthorizedPermission')
+  })
+})
@@ -0,0 +1,70 @@
+import { expect } from 'chai'
+import { NATIVE_MINT, TOKEN_PROGRAM_ID } from '@solana/spl-token'
+import { LAMPORTS_PER_SOL } from '@solana/web3.js'
+import BN from 'bn.js'
+import { createFixture, Fixture } from './fixtures/tokenized-vault-fixture'
+import { MintWrapper, wrapMint } from './fixtures/spl'
+import { nextSlot } from './fixtures/utils'
+import { ADDRESSES, INIT_OPTIONS } from '../shared/tokenized-vault.constants'
+
+// Signature fee charged to the user for each transaction
+const TX_FEE = 5000n
+
+describe('native SOL vault', () => {
+  let fixture: Fixture
+  let wsolMint: MintWrapper
+  let sharesMint: MintWrapper
+  const accounts = { assetTokenProgram: TOKEN_PROGRAM_ID, userAccess: null }
+
+  beforeEach(async () => {
+    fixture = await createFixture({ createConfig: false })
+    await fixture.program.methods
+      .initialize({ name: 'Staked SOL', symbol: 'vSOL', uri: 'https://test.com' }, INIT_OPTIONS.NONE)
+      .accounts({ assetMint: NATIVE_MINT, assetTokenProgram: TOKEN_PROGRAM_ID })
+      .rpc()
+    wsolMint = wrapMint(fixture.provider, NATIVE_MINT)
+    sharesMint = wrapMint(fixture.provider, ADDRESSES.SHARES_MINT)
+  })
+
+  it('wraps deposited lamports into the asset vault', async () => {
+    const { provider, program, users } = fixture
+    const user = users[0]
+    const lamports = new BN(LAMPORTS_PER_SOL)
+
+    const previewShares = await program.methods.previewDeposit(lamports).accounts(accounts).view()
+    const before = provider.client.getBalance(user.publicKey)
+    await user.program.methods.depositSol(lamports).accounts(accounts).rpc()
+
+    expect(wsolMint.balanceOf(ADDRESSES.SHARES_MINT)).to.equal(BigInt(LAMPORTS_PER_SOL))
+    expect(sharesMint.balanceOf(user.publicKey).toString()).to.equal(previewShares.toString())
+    expect(before - provider.client.getBalance(user.publicKey)).to.be.greaterThan(BigInt(LAMPORTS_PER_SOL))
+  })
+
+  it('unwraps redeemed assets into the user system account', async () => {
+    const { provider, program, users } = fixture
+    const user = users[0]
+    await user.program.methods.depositSol(new BN(LAMPORTS_PER_SOL)).accounts(accounts).rpc()
+    nextSlot(provider)
+
+    const shares = new BN((sharesMint.balanceOf(user.publicKey) / 2n).toString())
+    const previewLamports = await program.methods.previewRedeem(shares).accounts(accounts).view()
+    const redeemed = BigInt(previewLamports.toString())
+    const before = provider.client.getBalance(user.publicKey)
+    await user.program.methods.withdrawSol(shares).accounts(accounts).rpc()
+
+    expect(provider.client.getBalance(user.publicKey) - before + TX_FEE).to.equal(redeemed)
+    expect(wsolMint.balanceOf(ADDRESSES.SHARES_MINT)).to.equal(BigInt(LAMPORTS_PER_SOL) - redeemed)
+    expect(provider.client.getAccount(ADDRESSES.UNWRAP(user.publicKey))).to.be.null
+  })
+
+  it('rejects SOL instructions on non-native vaults', async () => {
+    const { users, assetMint, cmmAccounts } = await createFixture()
+
+    await expect(
+      users[0].program.methods
+        .depositSol(new BN(LAMPORTS_PER_SOL))
+        .accountsPartial({ ...cmmAccounts, assetMint: assetMint.publicKey })
+        .rpc()
+    ).to.be.rejectedWith('NotNativeMint')
+  })
+})
@@ -0,0 +1,116 @@
+import { expect } from 'chai'
+import { PublicKey } from '@solana/web3.js'
+import BN from 'bn.js'
+import { createFixture, Fixture, NOW } from './fixtures/tokenized-vault-fixture'
+import { createMockStrategy, MockStrategyFixture } from './fixtures/mock-strategy'
+import { jumpToTimestamp } from './fixtures/utils'
+import { ADDRESSES, PERMISSIONS } from '../shared/tokenized-vault.constants'
+import { DAY_SECONDS } from '../shared/utils'
+
+describe('profit unlocking', () => {
+  let fixture: Fixture
+  let mock: MockStrategyFixture
+
+  beforeEach(async () => {
+    fixture = await createFixture()
+    const { program, admin, users, assetMint } = fixture
+    for (const permission of [PERMISSIONS.STRATEGY_MANAGER, PERMISSIONS.DEBT_MANAGER, PERMISSIONS.KEEPER]) {
+      await program.methods.grantPermission(permission).accounts({ to: admin.publicKey }).rpc()
+    }
+    await program.methods.setProfitMaxUnlockTime(new BN(DAY_SECONDS)).rpc()
+    mock = await createMockStrategy(fixture)
+    await users[0].program.methods.deposit(assetMint.parseAmount(1000)).accounts(users[0].accounts).rpc()
+    await mock.add(assetMint.parseAmount(1000))
+    await mock.updateDebt(assetMint.parseAmount(600))
+  })
+
+  // Assets redeemable for all the shares of `owner`
+  const assetsOf = async (owner: PublicKey) => {
+    const { program, sharesMint, cmmAccounts } = fixture
+    const shares = new BN(sharesMint.balanceOf(owner).toString())
+    return Number(await program.methods.previewRedeem(shares).accounts(cmmAccounts).view())
+  }
+  const lockedShares = async () => {
+    const { program, cmmAccounts } = fixture
+    return BigInt((await program.methods.lockedShares().accounts(cmmAccounts).view()).toString())
+  }
+  const totalSupply = async () => {
+    const { program, cmmAccounts } = fixture
+    return BigInt((await program.methods.totalSupply().accounts(cmmAccounts).view()).toString())
+  }
+  const jumpBy = (seconds: number) => jumpToTimestamp(fixture.provider, NOW.getTime() / 1000 + seconds)
+
+  it('locks reported profit', async () => {
+    const { users, assetMint, sharesMint } = fixture
+    await mock.simulateGain(assetMint.parseAmount(100))
+    await mock.report()
+
+    expect(await assetsOf(users[0].publicKey)).to.be.closeTo(Number(assetMint.parseAmount(1000)), 2)
+    expect(await lockedShares()).to.equal(sharesMint.balanceOfTokenAccount(ADDRESSES.LOCKED_SHARES))
+    expect(await lockedShares() > 0n).to.be.true
+    expect(await totalSupply()).to.equal(sharesMint.supply())
+  })
+
+  it('unlocks profit linearly', async () => {
+    const { users, assetMint, sharesMint } = fixture
+    await mock.simulateGain(assetMint.parseAmount(100))
+    await mock.report()
+    const locked = await lockedShares()
+
+    jumpBy(DAY_SECONDS / 2)
+    expect(await assetsOf(users[0].publicKey)).to.be.closeTo(Number(assetMint.parseAmount(1050)), 10)
+    expect(Number(await lockedShares())).to.be.closeTo(Number(locked / 2n), 1)
+    expect(await totalSupply() < sharesMint.supply()).to.be.true
+
+    jumpBy(DAY_SECONDS)
+    expect(await assetsOf(users[0].publicKey)).to.be.closeTo(Number(assetMint.parseAmount(1100)), 2)
+    expect(await lockedShares()).to.equal(0n)
+    expect(await totalSupply()).to.equal(sharesMint.balanceOf(users[0].publicKey))
+  })
+
+  it('burns unlocked shares on the next report', async () => {
+    const { users, assetMint, sharesMint } = fixture
+    await mock.simulateGain(assetMint.parseAmount(100))
+    await mock.report()
+
+    jumpBy(DAY_SECONDS)
+    await mock.report()
+    expect(sharesMint.balanceOfTokenAccount(ADDRESSES.LOCKED_SHARES)).to.equal(0n)
+    expect(sharesMint.supply()).to.equal(sharesMint.balanceOf(users[0].publicKey))
+    expect(await assetsOf(users[0].publicKey)).to.be.closeTo(Number(assetMint.parseAmount(1100)), 2)
+  })
+
+  it('locks the profit net of the performance fee', async () => {
+    const { program, users, assetMint, sharesMint } = fixture
+    const feeRecipient = users[1].publicKey
+    await program.methods.setPerformanceFee(new BN(1000), feeRecipient).rpc()
+    await sharesMint.createAtaOf(feeRecipient)
+    await mock.simulateGain(assetMint.parseAmount(100))
+    await mock.report(sharesMint.getAtaOf(feeRecipient))
+
+    expect(await assetsOf(feeRecipient)).to.be.closeTo(Number(assetMint.parseAmount(10)), 2)
+    expect(await assetsOf(users[0].publicKey)).to.be.closeTo(Number(assetMint.parseAmount(1000)), 2)
+
+    jumpBy(DAY_SECONDS)
+    expect(await assetsOf(users[0].publicKey)).to.be.closeTo(Number(assetMint.parseAmount(1090)), 2)
+  })
+
+  it('absorbs losses with the locked profit', async () => {
+    const { users, assetMint } = fixture
+    await mock.simulateGain(assetMint.parseAmount(100))
+    await mock.report()
+    const locked = await lockedShares()
+
+    await mock.simulateLoss(assetMint.parseAmount(50))
+    await mock.report()
+    expect(await assetsOf(users[0].publicKey)).to.be.closeTo(Number(assetMint.parseAmount(1000)), 2)
+    expect(Number(await lockedShares())).to.be.closeTo(Number(locked / 2n), 1_000)
+  })
+
+  it('rejects an unlock time above one year', async () => {
+    const { program } = fixture
+    await expect(program.methods.setProfitMaxUnlockTime(new BN(366 * DAY_SECONDS)).rpc()).to.be.rejectedWith(
+      'InvalidProfitUnlockTime'
+    )
+  })
+})
@@ -0,0 +1,91 @@
+import { expect } from 'chai'
+import BN from 'bn.js'
+import { createFixture, Fixture, NOW } from './fixtures/tokenized-vault-fixture'
+import { jumpToTimestamp, nextSlot } from './fixtures/utils'
+import { ADDRESSES, PERMISSIONS } from '../shared/tokenized-vault.constants'
+import { DAY_SECONDS } from '../shared/utils'
+
+describe('withdrawal rate limit', () => {
+  let fixture: Fixture
+
+  beforeEach(async () => {
+    fixture = await createFixture()
+    const { provider, program, admin, users, assetMint } = fixture
+    await program.methods.grantPermission(PERMISSIONS.GUARDIAN).accounts({ to: admin.publicKey }).rpc()
+    // 20% of TVL per 24h
+    await admin.program.methods
+      .setWithdrawRateLimit(new BN(2000), new BN(DAY_SECONDS))
+      .accounts({ access: ADDRESSES.ACCESS(admin.publicKey) })
+      .rpc()
+    await users[0].program.methods.deposit(assetMint.parseAmount(100)).accounts(users[0].accounts).rpc()
+    nextSlot(provider)
+  })
+
+  it('rejects withdrawals above the window limit', async () => {
+    const { users, assetMint, sharesMint } = fixture
+    const user = users[0]
+    await user.program.methods.withdraw(assetMint.parseAmount(15)).accounts(user.accounts).rpc()
+
+    await expect(
+      user.program.methods.withdraw(assetMint.parseAmount(10)).accounts(user.accounts).rpc()
+    ).to.be.rejectedWith('WithdrawalRateLimited')
+    await expect(
+      user.program.methods.redeem(sharesMint.parseAmount(10)).accounts(user.accounts).rpc()
+    ).to.be.rejectedWith('WithdrawalRateLimited')
+  })
+
+  it('frees capacity as the window elapses', async () => {
+    const { provider, users, assetMint } = fixture
+    const user = users[0]
+    await user.program.methods.withdraw(assetMint.parseAmount(15)).accounts(user.accounts).rpc()
+
+    jumpToTimestamp(provider, NOW.getTime() / 1000 + DAY_SECONDS)
+    await user.program.methods.withdraw(assetMint.parseAmount(10)).accounts(user.accounts).rpc()
+  })
+
+  it('lets the guardian reset the bucket', async () => {
+    const { admin, users, assetMint } = fixture
+    const user = users[0]
+    await user.program.methods.withdraw(assetMint.parseAmount(15)).accounts(user.accounts).rpc()
+
+    await admin.program.methods.resetWithdrawRateLimit().accounts({ access: ADDRESSES.ACCESS(admin.publicKey) }).rpc()
+    await user.program.methods.withdraw(assetMint.parseAmount(10)).accounts(user.accounts).rpc()
+  })
+
+  it('limits assets filled by async redemptions', async () => {
+    const { program, admin, users, assetMint, sharesMint, cmmAccounts } = fixture
+    const user = users[0]
+    await program.methods.grantPermission(PERMISSIONS.KEEPER).accounts({ to: admin.publicKey }).rpc()
+    await user.program.methods.requestRedeem(sharesMint.parseAmount(50)).accounts(cmmAccounts).rpc()
+    await admin.program.methods
+      .closeWithdrawalEpoch()
+      .accounts({
+        ...cmmAccounts,
+        access: ADDRESSES.ACCESS(admin.publicKey),
+        withdrawalEpoch: ADDRESSES.WITHDRAWAL_EPOCH(0),
+      })
+      .rpc()
+
+    // Only 20% of the TVL is filled, the rest carries forward
+    const viewAccounts = {
+      user: user.publicKey,
+      redeemRequest: ADDRESSES.REDEEM_REQUEST(user.publicKey),
+      withdrawalEpoch: ADDRESSES.WITHDRAWAL_EPOCH(0),
+    }
+    const claimable = await program.methods.claimableRedeemRequest().accounts(viewAccounts).view()
+    expect(Number(claimable)).to.be.closeTo(Number(sharesMint.parseAmount(20)), 1_000)
+    await expect(
+      user.program.methods.withdraw(assetMint.parseAmount(1)).accounts(user.accounts).rpc()
+    ).to.be.rejectedWith('WithdrawalRateLimited')
+  })
+
+  it('rejects changes without GUARDIAN permission', async () => {
+    const { users } = fixture
+    await expect(
+      users[1].program.methods
+        .setWithdrawRateLimit(new BN(10000), new BN(DAY_SECONDS))
+        .accounts({ access: null })
+        .rpc()
+    ).to.be.rejectedWith('UnauthorizedPermission')
+  })
+})
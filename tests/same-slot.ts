@@ -0,0 +1,70 @@
+import { expect } from 'chai'
+import { createFixture, Fixture } from './fixtures/tokenized-vault-fixture'
+import { createMockStrategy } from './fixtures/mock-strategy'
+import { nextSlot } from './fixtures/utils'
+import { PERMISSIONS } from '../shared/tokenized-vault.constants'
+
+describe('same-slot guard', () => {
+  let fixture: Fixture
+
+  beforeEach(async () => {
+    fixture = await createFixture()
+  })
+
+  it('rejects withdraw and redeem in the slot of a deposit', async () => {
+    const { provider, users, assetMint, sharesMint } = fixture
+    const user = users[0]
+    await user.program.methods.deposit(assetMint.parseAmount(10)).accounts(user.accounts).rpc()
+
+    await expect(
+      user.program.methods.withdraw(assetMint.parseAmount(1)).accounts(user.accounts).rpc()
+    ).to.be.rejectedWith('SameSlotWithdrawal')
+    await expect(
+      user.program.methods.redeem(sharesMint.parseAmount(1)).accounts(user.accounts).rpc()
+    ).to.be.rejectedWith('SameSlotWithdrawal')
+
+    nextSlot(provider)
+    await user.program.methods.redeem(sharesMint.parseAmount(1)).accounts(user.accounts).rpc()
+  })
+
+  it('rejects redeem requests in the slot of a deposit', async () => {
+    const { provider, users, assetMint, sharesMint, cmmAccounts } = fixture
+    const user = users[0]
+    await user.program.methods.deposit(assetMint.parseAmount(10)).accounts(user.accounts).rpc()
+
+    await expect(
+      user.program.methods.requestRedeem(sharesMint.parseAmount(1)).accounts(cmmAccounts).rpc()
+    ).to.be.rejectedWith('SameSlotWithdrawal')
+
+    nextSlot(provider)
+    await user.program.methods.requestRedeem(sharesMint.parseAmount(1)).accounts(cmmAccounts).rpc()
+  })
+
+  it('rejects withdrawals of every holder after a report in its slot', async () => {
+    const { provider, program, admin, users, assetMint, sharesMint, cmmAccounts } = fixture
+    const [holder, depositor] = users
+    for (const permission of [PERMISSIONS.STRATEGY_MANAGER, PERMISSIONS.DEBT_MANAGER, PERMISSIONS.KEEPER]) {
+      await program.methods.grantPermission(permission).accounts({ to: admin.publicKey }).rpc()
+    }
+    await program.methods.setReportSlotGuard(true).rpc()
+    const mock = await createMockStrategy(fixture)
+    await mock.add(assetMint.parseAmount(1000))
+    await holder.program.methods.deposit(assetMint.parseAmount(100)).accounts(holder.accounts).rpc()
+    await mock.updateDebt(assetMint.parseAmount(50))
+    nextSlot(provider)
+
+    // Shares deposited earlier in the slot could reach the holder's wallet before the report
+    await depositor.program.methods.deposit(assetMint.parseAmount(10)).accounts(depositor.accounts).rpc()
+    await mock.simulateGain(assetMint.parseAmount(10))
+    await mock.report()
+    await expect(
+      holder.program.methods.redeem(sharesMint.parseAmount(1)).accounts(holder.accounts).rpc()
+    ).to.be.rejectedWith('WithdrawalInReportSlot')
+    await expect(
+      holder.program.methods.requestRedeem(sharesMint.parseAmount(1)).accounts(cmmAccounts).rpc()
+    ).to.be.rejectedWith('WithdrawalInReportSlot')
+
+    nextSlot(provider)
+    await holder.program.methods.redeem(sharesMint.parseAmount(1)).accounts(holder.accounts).rpc()
+  })
+})
@@ -0,0 +1,44 @@
+import { expect } from 'chai'
+import { getScaledUiAmountConfig, unpackMint } from '@solana/spl-token'
+import { createFixture, Fixture } from './fixtures/tokenized-vault-fixture'
+import { ADDRESSES, INIT_OPTIONS, PERMISSIONS } from '../shared/tokenized-vault.constants'
+
+describe('share multiplier', () => {
+  let fixture: Fixture
+
+  beforeEach(async () => {
+    fixture = await createFixture({ initOptions: INIT_OPTIONS.SHARES_SCALED_UI_AMOUNT })
+    const { program, admin } = fixture
+    await program.methods.grantPermission(PERMISSIONS.KEEPER).accounts({ to: admin.publicKey }).rpc()
+  })
+
+  const scaledUiAmountConfig = () => {
+    const { client, sharesMint } = fixture
+    const account = client.getAccount(sharesMint.publicKey) as any
+    return getScaledUiAmountConfig(unpackMint(sharesMint.publicKey, account, sharesMint.tokenProgramId))!
+  }
+
+  it('starts at one asset per share', async () => {
+    expect(scaledUiAmountConfig().multiplier).to.equal(1)
+  })
+
+  it('refreshes the multiplier from the share price', async () => {
+    const { provider, users, admin, assetMint, sharesMint, cmmAccounts } = fixture
+    await users[0].program.methods.deposit(assetMint.parseAmount(100)).accounts(users[0].accounts).rpc()
+    // Yield doubles the share price
+    await assetMint.transferTo(provider, sharesMint.publicKey, assetMint.parseAmountBigInt(100))
+
+    await admin.program.methods
+      .updateShareMultiplier()
+      .accounts({ ...cmmAccounts, access: ADDRESSES.ACCESS(admin.publicKey) })
+      .rpc()
+    expect(scaledUiAmountConfig().newMultiplier).to.be.closeTo(2, 0.001)
+  })
+
+  it('rejects refreshes without KEEPER permission', async () => {
+    const { users, cmmAccounts } = fixture
+    await expect(
+      users[0].program.methods.updateShareMultiplier().accounts({ ...cmmAccounts, access: null }).rpc()
+    ).to.be.rejectedWith('UnauthorizedPermission')
+  })
+})
@@ -0,0 +1,43 @@
+import { expect } from 'chai'
+import { createFixture, Fixture } from './fixtures/tokenized-vault-fixture'
+import { nextSlot } from './fixtures/utils'
+import { INIT_OPTIONS } from '../shared/tokenized-vault.constants'
+
+describe('non-transferable shares', () => {
+  let fixture: Fixture
+
+  beforeEach(async () => {
+    fixture = await createFixture({ initOptions: INIT_OPTIONS.SHARES_NON_TRANSFERABLE })
+  })
+
+  it('deposits and redeems soulbound shares', async () => {
+    const { provider, users, assetMint, sharesMint } = fixture
+    const user = users[0]
+    await user.program.methods.deposit(assetMint.parseAmount(10)).accounts(user.accounts).rpc()
+    expect(sharesMint.balanceOf(user.publicKey)).to.equal(sharesMint.parseAmountBigInt(10))
+
+    nextSlot(provider)
+    await user.program.methods.redeem(sharesMint.parseAmount(4)).accounts(user.accounts).rpc()
+    await user.program.methods.withdraw(assetMint.parseAmount(6)).accounts(user.accounts).rpc()
+    expect(sharesMint.balanceOf(user.publicKey)).to.equal(0n)
+  })
+
+  it('rejects share transfers', async () => {
+    const { provider, program, deployer, users, assetMint, sharesMint } = fixture
+    await program.methods.deposit(assetMint.parseAmount(10)).accounts(deployer.accounts).rpc()
+
+    await expect(sharesMint.transferTo(provider, users[0].publicKey, sharesMint.parseAmount(1))).to.be.rejected
+    expect(sharesMint.balanceOf(users[0].publicKey)).to.equal(0n)
+  })
+
+  it('rejects asynchronous requests', async () => {
+    const { program, users, assetMint, sharesMint, cmmAccounts } = fixture
+    const user = users[0]
+    await user.program.methods.deposit(assetMint.parseAmount(10)).accounts(user.accounts).rpc()
+
+    await expect(
+      user.program.methods.requestRedeem(sharesMint.parseAmount(1)).accounts(cmmAccounts).rpc()
+    ).to.be.rejectedWith('NonTransferableShares')
+    await expect(program.methods.setAsyncDeposits(true).rpc()).to.be.rejectedWith('NonTransferableShares')
+  })
+})
@@ -0,0 +1,96 @@
+import { expect } from 'chai'
+import { PublicKey } from '@solana/web3.js'
+import BN from 'bn.js'
+import { createFixture, Fixture } from './fixtures/tokenized-vault-fixture'
+import { createMockStrategy, MockStrategyFixture } from './fixtures/mock-strategy'
+import { ADDRESSES, PERMISSIONS } from '../shared/tokenized-vault.constants'
+
+describe('strategy reports', () => {
+  let fixture: Fixture
+  let mock: MockStrategyFixture
+
+  beforeEach(async () => {
+    fixture = await createFixture()
+    const { program, admin, users, assetMint } = fixture
+    for (const permission of [PERMISSIONS.STRATEGY_MANAGER, PERMISSIONS.DEBT_MANAGER, PERMISSIONS.KEEPER]) {
+      await program.methods.grantPermission(permission).accounts({ to: admin.publicKey }).rpc()
+    }
+    mock = await createMockStrategy(fixture)
+    await users[0].program.methods.deposit(assetMint.parseAmount(1000)).accounts(users[0].accounts).rpc()
+    await mock.add(assetMint.parseAmount(1000))
+    await mock.updateDebt(assetMint.parseAmount(600))
+  })
+
+  // Assets redeemable for all the shares of `owner`
+  const assetsOf = async (owner: PublicKey) => {
+    const { program, sharesMint, cmmAccounts } = fixture
+    const shares = new BN(sharesMint.balanceOf(owner).toString())
+    return Number(await program.methods.previewRedeem(shares).accounts(cmmAccounts).view())
+  }
+
+  it('records gains as debt', async () => {
+    const { client, program, users, assetMint } = fixture
+    await mock.simulateGain(assetMint.parseAmount(60))
+    await mock.report()
+
+    const strategy = await program.account.strategy.fetch(mock.strategy)
+    expect(strategy.currentDebt.toString()).to.equal(assetMint.parseAmount(660).toString())
+    const config = await program.account.config.fetch(ADDRESSES.CONFIG)
+    expect(config.totalDebt.toString()).to.equal(assetMint.parseAmount(660).toString())
+    expect(config.lastReportSlot.toString()).to.equal(client.getClock().slot.toString())
+    expect(await assetsOf(users[0].publicKey)).to.be.closeTo(Number(assetMint.parseAmount(1060)), 2)
+  })
+
+  it('charges the performance fee on gains', async () => {
+    const { program, users, assetMint, sharesMint } = fixture
+    const feeRecipient = users[1].publicKey
+    await program.methods.setPerformanceFee(new BN(1000), feeRecipient).rpc()
+    await sharesMint.createAtaOf(feeRecipient)
+
+    await mock.simulateGain(assetMint.parseAmount(100))
+    await mock.report(sharesMint.getAtaOf(feeRecipient))
+
+    expect(await assetsOf(feeRecipient)).to.be.closeTo(Number(assetMint.parseAmount(10)), 2)
+    expect(await assetsOf(users[0].publicKey)).to.be.closeTo(Number(assetMint.parseAmount(1090)), 2)
+  })
+
+  it('requires the fee recipient account when fees are charged', async () => {
+    const { program, users, assetMint } = fixture
+    await program.methods.setPerformanceFee(new BN(1000), users[1].publicKey).rpc()
+    await mock.simulateGain(assetMint.parseAmount(100))
+
+    await expect(mock.report()).to.be.rejectedWith('MissingFeeRecipient')
+  })
+
+  it('socialises losses across share holders', async () => {
+    const { program, users, assetMint } = fixture
+    // No fees are charged on losses, so the fee recipient account is not needed
+    await program.methods.setPerformanceFee(new BN(1000), users[1].publicKey).rpc()
+    await mock.simulateLoss(assetMint.parseAmount(100))
+    await mock.report()
+
+    const config = await program.account.config.fetch(ADDRESSES.CONFIG)
+    expect(config.totalDebt.toString()).to.equal(assetMint.parseAmount(500).toString())
+    expect(await assetsOf(users[0].publicKey)).to.be.closeTo(Number(assetMint.parseAmount(900)), 2)
+  })
+
+  it('publishes the performance fee in the shares metadata', async () => {
+    const { program, users, sharesMint } = fixture
+    await program.methods.setPerformanceFee(new BN(1000), users[1].publicKey).rpc()
+
+    expect(sharesMint.tokenMetadata()!.additionalMetadata).to.deep.include(['performance_fee_bps', '1000'])
+  })
+
+  it('rejects a performance fee above 100%', async () => {
+    const { program, users } = fixture
+    await expect(program.methods.setPerformanceFee(new BN(10_001), users[1].publicKey).rpc()).to.be.rejectedWith(
+      'InvalidPerformanceFee'
+    )
+  })
+
+  it('rejects reports without KEEPER permission', async () => {
+    const { program, admin } = fixture
+    await program.methods.revokePermission(PERMISSIONS.KEEPER).accounts({ to: admin.publicKey }).rpc()
+    await expect(mock.report()).to.be.rejectedWith('UnauthorizedPermission')
+  })
+})
@@ -0,0 +1,138 @@
+import { expect } from 'chai'
+import BN from 'bn.js'
+import { createFixture, Fixture } from './fixtures/tokenized-vault-fixture'
+import { createMockStrategy, MockStrategyFixture } from './fixtures/mock-strategy'
+import { nextSlot } from './fixtures/utils'
+import { ADDRESSES, PERMISSIONS } from '../shared/tokenized-vault.constants'
+
+describe('strategies', () => {
+  let fixture: Fixture
+  let mock: MockStrategyFixture
+
+  beforeEach(async () => {
+    fixture = await createFixture()
+    const { program, admin } = fixture
+    await program.methods.grantPermission(PERMISSIONS.STRATEGY_MANAGER).accounts({ to: admin.publicKey }).rpc()
+    await program.methods.grantPermission(PERMISSIONS.DEBT_MANAGER).accounts({ to: admin.publicKey }).rpc()
+    mock = await createMockStrategy(fixture)
+  })
+
+  describe('registry', () => {
+    it('adds a strategy', async () => {
+      const { program } = fixture
+      await mock.add(new BN(1_000_000))
+
+      const strategy = await program.account.strategy.fetch(mock.strategy)
+      expect(strategy.vault.toBase58()).to.equal(ADDRESSES.CONFIG.toBase58())
+      expect(strategy.assetAccount.toBase58()).to.equal(mock.assetAccount.toBase58())
+      expect(strategy.maxDebt.toString()).to.equal('1000000')
+      expect(strategy.currentDebt.toString()).to.equal('0')
+    })
+
+    it('updates the max debt', async () => {
+      const { program, admin } = fixture
+      await mock.add(new BN(1_000_000))
+      await admin.program.methods
+        .updateMaxDebt(new BN(5_000_000))
+        .accounts({ access: mock.accounts.access, strategyProgram: mock.programId })
+        .rpc()
+
+      const strategy = await program.account.strategy.fetch(mock.strategy)
+      expect(strategy.maxDebt.toString()).to.equal('5000000')
+    })
+
+    it('removes a strategy without debt', async () => {
+      const { provider } = fixture
+      await mock.add(new BN(1_000_000))
+      await mock.remove()
+
+      expect(provider.client.getAccount(mock.strategy)).to.be.null
+    })
+
+    it('rejects strategy changes without STRATEGY_MANAGER permission', async () => {
+      const { users } = fixture
+      await expect(
+        users[0].program.methods.addStrategy(new BN(1_000_000)).accounts({ ...mock.accounts, access: null }).rpc()
+      ).to.be.rejectedWith('UnauthorizedPermission')
+    })
+  })
+
+  describe('debt allocation', () => {
+    beforeEach(async () => {
+      const { users, assetMint } = fixture
+      await users[0].program.methods.deposit(assetMint.parseAmount(1000)).accounts(users[0].accounts).rpc()
+      await mock.add(assetMint.parseAmount(800))
+    })
+
+    it('allocates idle assets to the strategy', async () => {
+      const { program, assetMint, sharesMint, cmmAccounts } = fixture
+      const shares = sharesMint.parseAmount(100)
+      const before = await program.methods.previewRedeem(shares).accounts(cmmAccounts).view()
+      await mock.updateDebt(assetMint.parseAmount(600))
+
+      expect(mock.balance()).to.equal(assetMint.parseAmountBigInt(600))
+      expect(assetMint.balanceOf(ADDRESSES.SHARES_MINT)).to.equal(assetMint.parseAmountBigInt(400))
+      const config = await program.account.config.fetch(ADDRESSES.CONFIG)
+      expect(config.totalDebt.toString()).to.equal(assetMint.parseAmount(600).toString())
+      // Debt still counts towards the share price
+      const after = await program.methods.previewRedeem(shares).accounts(cmmAccounts).view()
+      expect(after.toString()).to.equal(before.toString())
+    })
+
+    it('caps the debt at the max debt', async () => {
+      const { program, assetMint } = fixture
+      await mock.updateDebt(assetMint.parseAmount(1000))
+
+      expect(mock.balance()).to.equal(assetMint.parseAmountBigInt(800))
+      const strategy = await program.account.strategy.fetch(mock.strategy)
+      expect(strategy.currentDebt.toString()).to.equal(assetMint.parseAmount(800).toString())
+    })
+
+    it('recalls debt from the strategy', async () => {
+      const { program, assetMint } = fixture
+      await mock.updateDebt(assetMint.parseAmount(600))
+      await mock.updateDebt(assetMint.parseAmount(200))
+
+      expect(mock.balance()).to.equal(assetMint.parseAmountBigInt(200))
+      expect(assetMint.balanceOf(ADDRESSES.SHARES_MINT)).to.equal(assetMint.parseAmountBigInt(800))
+      const config = await program.account.config.fetch(ADDRESSES.CONFIG)
+      expect(config.totalDebt.toString()).to.equal(assetMint.parseAmount(200).toString())
+    })
+
+    it('removes a strategy only once its debt is repaid', async () => {
+      const { provider, assetMint } = fixture
+      await mock.updateDebt(assetMint.parseAmount(600))
+      await expect(mock.remove()).to.be.rejectedWith('StrategyHasDebt')
+
+      await mock.updateDebt(new BN(0))
+      await mock.remove()
+      expect(provider.client.getAccount(mock.strategy)).to.be.null
+    })
+
+    it('limits withdrawals to idle assets', async () => {
+      const { provider, program, users, assetMint, sharesMint, cmmAccounts } = fixture
+      const user = users[0]
+      await mock.updateDebt(assetMint.parseAmount(800))
+      nextSlot(provider)
+
+      const accounts = {
+        ...cmmAccounts,
+        user: user.publicKey,
+        userPosition: ADDRESSES.USER_POSITION(user.publicKey),
+        userSharesVault: sharesMint.getAtaOf(user.publicKey),
+      }
+      expect((await program.methods.maxWithdraw().accounts(accounts).view()).toString()).to.equal(
+        assetMint.parseAmount(200).toString()
+      )
+      await expect(
+        user.program.methods.withdraw(assetMint.parseAmount(300)).accounts(user.accounts).rpc()
+      ).to.be.rejectedWith('InsufficientIdleAssets')
+    })
+
+    it('rejects debt updates without DEBT_MANAGER permission', async () => {
+      const { program, admin, assetMint } = fixture
+      await program.methods.revokePermission(PERMISSIONS.DEBT_MANAGER).accounts({ to: admin.publicKey }).rpc()
+      await expect(mock.updateDebt(assetMint.parseAmount(600))).to.be.rejectedWith('UnauthorizedPermission')
+    })
+  })
+})
@@ -0,0 +1,87 @@
+import { expect } from 'chai'
+import { createApproveInstruction } from '@solana/spl-token'
+import { Transaction } from '@solana/web3.js'
+import { createFixture, Fixture } from './fixtures/tokenized-vault-fixture'
+import { nextSlot } from './fixtures/utils'
+
+describe('arbitrary token accounts', () => {
+  let fixture: Fixture
+
+  beforeEach(async () => {
+    fixture = await createFixture()
+  })
+
+  it('deposits from a non-associated token account of the user', async () => {
+    const { provider, users, assetMint, sharesMint } = fixture
+    const user = users[0]
+    const tokenAccount = await assetMint.createTokenAccount(user.publicKey)
+    await assetMint.transferToTokenAccount(provider, tokenAccount, assetMint.parseAmount(10))
+
+    await user.program.methods
+      .deposit(assetMint.parseAmount(10))
+      .accounts({ ...user.accounts, userAssetVault: tokenAccount })
+      .rpc()
+
+    expect(assetMint.balanceOfTokenAccount(tokenAccount)).to.equal(0n)
+    expect(sharesMint.balanceOf(user.publicKey)).to.equal(sharesMint.parseAmountBigInt(10))
+  })
+
+  it('lets an approved delegate deposit from the owner account', async () => {
+    const { users, assetMint, sharesMint } = fixture
+    const [delegate, owner] = users
+    const approve = createApproveInstruction(
+      owner.accounts.userAssetVault,
+      delegate.publicKey,
+      owner.publicKey,
+      assetMint.parseAmountBigInt(10),
+      [],
+      assetMint.tokenProgramId
+    )
+    await owner.provider.send(new Transaction().add(approve))
+
+    const before = assetMint.balanceOf(owner.publicKey)
+    await delegate.program.methods
+      .deposit(assetMint.parseAmount(10))
+      .accounts({ ...delegate.accounts, userAssetVault: owner.accounts.userAssetVault })
+      .rpc()
+
+    expect(before - assetMint.balanceOf(owner.publicKey)).to.equal(assetMint.parseAmountBigInt(10))
+    expect(sharesMint.balanceOf(delegate.publicKey)).to.equal(sharesMint.parseAmountBigInt(10))
+  })
+
+  it('rejects deposits from accounts the signer does not control', async () => {
+    const { users, assetMint } = fixture
+    const [user, victim] = users
+
+    await expect(
+      user.program.methods
+        .deposit(assetMint.parseAmount(1))
+        .accounts({ ...user.accounts, userAssetVault: victim.accounts.userAssetVault })
+        .rpc()
+    ).to.be.rejectedWith('InvalidTokenAccountAuthority')
+  })
+
+  it('withdraws to any receiver account', async () => {
+    const { provider, users, assetMint, sharesMint } = fixture
+    const [user, receiver] = users
+    await user.program.methods.deposit(assetMint.parseAmount(10)).accounts(user.accounts).rpc()
+    nextSlot(provider)
+    const receiverAccount = await assetMint.createTokenAccount(receiver.publicKey)
+
+    await user.program.methods
+      .withdraw(assetMint.parseAmount(4))
+      .accounts({
+        ...user.accounts,
+        userAssetVault: receiverAccount,
+        receiverBlocked: receiver.accounts.receiverBlocked,
+      })
+      .rpc()
+    await user.program.methods
+      .redeem(sharesMint.parseAmount(6))
+      .accounts({ ...user.accounts, ...receiver.accounts })
+      .rpc()
+
+    expect(assetMint.balanceOfTokenAccount(receiverAccount)).to.equal(assetMint.parseAmountBigInt(4))
+    expect(assetMint.balanceOf(receiver.publicKey)).to.equal(assetMint.parseAmountBigInt(100000006))
+  })
+})
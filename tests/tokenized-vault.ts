@@ -1,12 +1,13 @@
 import { expect } from 'chai'
 import { createFixture, Fixture, UserFixture } from './fixtures/tokenized-vault-fixture'
 import BN from 'bn.js'
+import { nextSlot } from './fixtures/utils'
 
 describe('tokenized-vault', () => {
   it('Mint', async () => {
-    const { users, provider, sharesMint, assetMint, cmmAccounts } = await createFixture()
+    const { users, provider, sharesMint, assetMint } = await createFixture()
     const user = users[0]
-    await expect(user.program.methods.mint(sharesMint.parseAmount(1)).accounts(cmmAccounts).rpc())
+    await expect(user.program.methods.mint(sharesMint.parseAmount(1)).accounts(user.accounts).rpc())
       .splBalancesHaveChanged(provider, sharesMint.publicKey, [
         { wallet: user.publicKey, amount: sharesMint.parseAmount(1) },
       ])
@@ -15,9 +16,9 @@ describe('tokenized-vault', () => {
       ])
   })
   it('Deposit', async () => {
-    const { users, provider, sharesMint, assetMint, cmmAccounts } = await createFixture()
+    const { users, provider, sharesMint, assetMint } = await createFixture()
     const user = users[0]
-    await expect(user.program.methods.deposit(assetMint.parseAmount(1)).accounts(cmmAccounts).rpc())
+    await expect(user.program.methods.deposit(assetMint.parseAmount(1)).accounts(user.accounts).rpc())
       .splBalancesHaveChanged(provider, sharesMint.publicKey, [
         { wallet: user.publicKey, amount: sharesMint.parseAmount(1) },
       ])
@@ -26,10 +27,11 @@ describe('tokenized-vault', () => {
       ])
   })
   it('Withdraw', async () => {
-    const { users, provider, sharesMint, assetMint, cmmAccounts } = await createFixture()
+    const { users, provider, sharesMint, assetMint } = await createFixture()
     const user = users[0]
-    await user.program.methods.deposit(assetMint.parseAmount(1)).accounts(cmmAccounts).rpc()
-    await expect(user.program.methods.withdraw(assetMint.parseAmount(1)).accounts(cmmAccounts).rpc())
+    await user.program.methods.deposit(assetMint.parseAmount(1)).accounts(user.accounts).rpc()
+    nextSlot(provider)
+    await expect(user.program.methods.withdraw(assetMint.parseAmount(1)).accounts(user.accounts).rpc())
       .splBalancesHaveChanged(provider, sharesMint.publicKey, [
         { wallet: user.publicKey, amount: -sharesMint.parseAmount(1) },
       ])
@@ -38,10 +40,11 @@ describe('tokenized-vault', () => {
       ])
   })
   it('Redeem', async () => {
-    const { users, provider, sharesMint, assetMint, cmmAccounts } = await createFixture()
+    const { users, provider, sharesMint, assetMint } = await createFixture()
     const user = users[0]
-    await user.program.methods.deposit(assetMint.parseAmount(1)).accounts(cmmAccounts).rpc()
-    await expect(user.program.methods.redeem(sharesMint.parseAmount(1)).accounts(cmmAccounts).rpc())
+    await user.program.methods.deposit(assetMint.parseAmount(1)).accounts(user.accounts).rpc()
+    nextSlot(provider)
+    await expect(user.program.methods.redeem(sharesMint.parseAmount(1)).accounts(user.accounts).rpc())
       .splBalancesHaveChanged(provider, sharesMint.publicKey, [
         { wallet: user.publicKey, amount: -sharesMint.parseAmount(1) },
       ])
@@ -50,7 +53,7 @@ describe('tokenized-vault', () => {
       ])
   })
   it('Complex scenario', async () => {
-    const { users, provider, sharesMint, assetMint, cmmAccounts } = await createFixture()
+    const { users, provider, sharesMint, assetMint } = await createFixture()
     //    Scenario:
     // No Action   USDA   SUSDA    USDA_vault   SUSDA_supply    Price
     // 1  Stake    +1000  +1000    1000         1000           1
@@ -66,7 +69,7 @@ describe('tokenized-vault', () => {
     // 1. Deposit 1000 assets
     const _1_assets = assetMint.parseAmount(1000)
     const _1_expected_shares = sharesMint.parseAmount(1000)
-    await expect(user.program.methods.deposit(_1_assets).accounts(cmmAccounts).rpc())
+    await expect(user.program.methods.deposit(_1_assets).accounts(user.accounts).rpc())
       .splBalancesHaveChanged(provider, sharesMint.publicKey, [{ wallet: user.publicKey, amount: _1_expected_shares }])
       .splBalancesHaveChanged(provider, assetMint.publicKey, [{ wallet: user.publicKey, amount: -_1_assets }])
 
@@ -76,7 +79,7 @@ describe('tokenized-vault', () => {
     // 3. Stake 2000 assets
     const _3_assets = assetMint.parseAmount(2000)
     const _3_expected_shares = sharesMint.parseAmount(1000)
-    await expect(user.program.methods.deposit(_3_assets).accounts(cmmAccounts).rpc()).splBalancesHaveChanged(
+    await expect(user.program.methods.deposit(_3_assets).accounts(user.accounts).rpc()).splBalancesHaveChanged(
       provider,
       sharesMint.publicKey,
       [{ wallet: user.publicKey, amount: _3_expected_shares }],
@@ -84,9 +87,10 @@ describe('tokenized-vault', () => {
     )
 
     // 4. Unstake 1000 assets
+    nextSlot(provider)
     const _4_shares = sharesMint.parseAmount(500)
     const _4_expected_assets = assetMint.parseAmount(1000)
-    await expect(user.program.methods.redeem(_4_shares).accounts(cmmAccounts).rpc()).splBalancesHaveChanged(
+    await expect(user.program.methods.redeem(_4_shares).accounts(user.accounts).rpc()).splBalancesHaveChanged(
       provider,
       assetMint.publicKey,
       [{ wallet: user.publicKey, amount: _4_expected_assets }],
@@ -99,7 +103,7 @@ describe('tokenized-vault', () => {
     // 6. Stake 4000 assets
     const _6_assets = assetMint.parseAmount(4000)
     const _6_expected_shares = sharesMint.parseAmount(1000)
-    await expect(user.program.methods.deposit(_6_assets).accounts(cmmAccounts).rpc()).splBalancesHaveChanged(
+    await expect(user.program.methods.deposit(_6_assets).accounts(user.accounts).rpc()).splBalancesHaveChanged(
       provider,
       sharesMint.publicKey,
       [{ wallet: user.publicKey, amount: _6_expected_shares }],
@@ -128,12 +132,12 @@ describe('tokenized-vault', () => {
     })
 
     it('deposit: virtual assets/shares mitigate inflation attack', async () => {
-      const { provider, sharesMint, assetMint, cmmAccounts, users } = fixture
+      const { provider, sharesMint, assetMint, users } = fixture
 
       const depositAssets = _1_asset
       const expectedShares = (depositAssets * effectiveShares) / effectiveAssets
 
-      await expect(user.program.methods.deposit(new BN(depositAssets)).accounts(cmmAccounts).rpc())
+      await expect(user.program.methods.deposit(new BN(depositAssets)).accounts(user.accounts).rpc())
         .splBalancesHaveChanged(provider, assetMint.publicKey, [
           { wallet: user.publicKey, amount: -depositAssets },
           { wallet: sharesMint.publicKey, amount: depositAssets },
@@ -142,13 +146,13 @@ describe('tokenized-vault', () => {
     })
 
     it('mint: protects against inflation attack but makes minting expensive', async () => {
-      const { users, provider, sharesMint, assetMint, cmmAccounts } = fixture
+      const { users, provider, sharesMint, assetMint } = fixture
 
       const mintShares = sharesMint.parseAmountBigInt(1)
       const expectedAssets = (mintShares * effectiveAssets) / effectiveShares
 
       await expect(
-        user.program.methods.mint(new BN(mintShares.toString())).accounts(cmmAccounts).rpc()
+        user.program.methods.mint(new BN(mintShares.toString())).accounts(user.accounts).rpc()
       ).splBalancesHaveChanged(provider, assetMint.publicKey, [
         { wallet: user.publicKey, amount: -expectedAssets },
         { wallet: sharesMint.publicKey, amount: expectedAssets },
@@ -0,0 +1,147 @@
+import { expect } from 'chai'
+import { createFixture, Fixture } from './fixtures/tokenized-vault-fixture'
+import { createMockStrategy } from './fixtures/mock-strategy'
+import { nextSlot } from './fixtures/utils'
+import { ADDRESSES, PERMISSIONS } from '../shared/tokenized-vault.constants'
+
+describe('transfer-fee asset', () => {
+  let fixture: Fixture
+
+  beforeEach(async () => {
+    // 1% transfer fee on the asset mint
+    fixture = await createFixture({ transferFeeBps: 100 })
+  })
+
+  it('mints shares against the assets the vault receives', async () => {
+    const { program, users, assetMint, sharesMint, cmmAccounts } = fixture
+    const user = users[0]
+    const vault = sharesMint.publicKey
+
+    const previewShares = await program.methods.previewDeposit(assetMint.parseAmount(1000)).accounts(cmmAccounts).view()
+    await user.program.methods.deposit(assetMint.parseAmount(1000)).accounts(user.accounts).rpc()
+
+    expect(assetMint.balanceOf(vault)).to.equal(assetMint.parseAmountBigInt(990))
+    expect(sharesMint.balanceOf(user.publicKey)).to.equal(sharesMint.parseAmountBigInt(990))
+    expect(previewShares.toString()).to.equal(sharesMint.parseAmount(990).toString())
+  })
+
+  it('charges the fee on top of the assets paid for a mint', async () => {
+    const { program, users, assetMint, sharesMint, cmmAccounts } = fixture
+    const user = users[0]
+
+    const previewAssets = await program.methods.previewMint(sharesMint.parseAmount(990)).accounts(cmmAccounts).view()
+    const before = assetMint.balanceOf(user.publicKey)
+    await user.program.methods.mint(sharesMint.parseAmount(990)).accounts(user.accounts).rpc()
+
+    expect(previewAssets.toString()).to.equal(assetMint.parseAmount(1000).toString())
+    expect(before - assetMint.balanceOf(user.publicKey)).to.equal(assetMint.parseAmountBigInt(1000))
+    expect(sharesMint.balanceOf(user.publicKey)).to.equal(sharesMint.parseAmountBigInt(990))
+  })
+
+  it('grosses up withdrawals so the user receives the requested assets', async () => {
+    const { provider, program, users, assetMint, sharesMint, cmmAccounts } = fixture
+    const user = users[0]
+    await user.program.methods.deposit(assetMint.parseAmount(1000)).accounts(user.accounts).rpc()
+    nextSlot(provider)
+
+    const previewShares = await program.methods.previewWithdraw(assetMint.parseAmount(500)).accounts(cmmAccounts).view()
+    const assetsBefore = assetMint.balanceOf(user.publicKey)
+    const sharesBefore = sharesMint.balanceOf(user.publicKey)
+    await user.program.methods.withdraw(assetMint.parseAmount(500)).accounts(user.accounts).rpc()
+
+    expect(assetMint.balanceOf(user.publicKey) - assetsBefore).to.equal(assetMint.parseAmountBigInt(500))
+    expect((sharesBefore - sharesMint.balanceOf(user.publicKey)).toString()).to.equal(previewShares.toString())
+  })
+
+  it('previews redeemed assets net of the fee', async () => {
+    const { provider, program, users, assetMint, sharesMint, cmmAccounts } = fixture
+    const user = users[0]
+    await user.program.methods.deposit(assetMint.parseAmount(1000)).accounts(user.accounts).rpc()
+    nextSlot(provider)
+
+    const previewAssets = await program.methods.previewRedeem(sharesMint.parseAmount(500)).accounts(cmmAccounts).view()
+    const before = assetMint.balanceOf(user.publicKey)
+    await user.program.methods.redeem(sharesMint.parseAmount(500)).accounts(user.accounts).rpc()
+
+    expect(previewAssets.toString()).to.equal(assetMint.parseAmount(495).toString())
+    expect(assetMint.balanceOf(user.publicKey) - before).to.equal(assetMint.parseAmountBigInt(495))
+  })
+
+  it('reports claimable redeem assets net of the fee', async () => {
+    const { provider, program, admin, users, assetMint, sharesMint, cmmAccounts } = fixture
+    const user = users[0]
+    await program.methods.grantPermission(PERMISSIONS.KEEPER).accounts({ to: admin.publicKey }).rpc()
+    await user.program.methods.deposit(assetMint.parseAmount(1000)).accounts(user.accounts).rpc()
+    nextSlot(provider)
+    await user.program.methods.requestRedeem(sharesMint.parseAmount(500)).accounts(cmmAccounts).rpc()
+    await admin.program.methods
+      .closeWithdrawalEpoch()
+      .accounts({
+        ...cmmAccounts,
+        access: ADDRESSES.ACCESS(admin.publicKey),
+        withdrawalEpoch: ADDRESSES.WITHDRAWAL_EPOCH(0),
+      })
+      .rpc()
+
+    const claimableAssets = await program.methods
+      .claimableRedeemAssets()
+      .accounts({
+        user: user.publicKey,
+        redeemRequest: ADDRESSES.REDEEM_REQUEST(user.publicKey),
+        withdrawalEpoch: ADDRESSES.WITHDRAWAL_EPOCH(0),
+      })
+      .view()
+    const before = assetMint.balanceOf(user.publicKey)
+    await user.program.methods
+      .claimRedeem()
+      .accounts({ ...cmmAccounts, withdrawalEpoch: ADDRESSES.WITHDRAWAL_EPOCH(0) })
+      .rpc()
+
+    expect(claimableAssets.toString()).to.equal(assetMint.parseAmount(495).toString())
+    expect(assetMint.balanceOf(user.publicKey) - before).to.equal(assetMint.parseAmountBigInt(495))
+  })
+  it('charges async deposits the fee once', async () => {
+    const { program, admin, users, assetMint, sharesMint, cmmAccounts } = fixture
+    const user = users[0]
+    await program.methods.grantPermission(PERMISSIONS.KEEPER).accounts({ to: admin.publicKey }).rpc()
+    await program.methods.setAsyncDeposits(true).rpc()
+    await user.program.methods.requestDeposit(assetMint.parseAmount(1000)).accounts(cmmAccounts).rpc()
+    expect(assetMint.balanceOf(sharesMint.publicKey)).to.equal(assetMint.parseAmountBigInt(990))
+
+    await admin.program.methods
+      .settleDeposits()
+      .accounts({ ...cmmAccounts, access: ADDRESSES.ACCESS(admin.publicKey), depositEpoch: ADDRESSES.DEPOSIT_EPOCH(0) })
+      .rpc()
+    expect(assetMint.balanceOf(sharesMint.publicKey)).to.equal(assetMint.parseAmountBigInt(990))
+    await user.program.methods.claimDeposit().accounts({ depositEpoch: ADDRESSES.DEPOSIT_EPOCH(0) }).rpc()
+    expect(sharesMint.balanceOf(user.publicKey)).to.equal(sharesMint.parseAmountBigInt(990))
+  })
+
+  it('refunds cancelled async deposits net of the outbound fee', async () => {
+    const { program, users, assetMint, sharesMint, cmmAccounts } = fixture
+    const user = users[0]
+    await program.methods.setAsyncDeposits(true).rpc()
+    const before = assetMint.balanceOf(user.publicKey)
+    await user.program.methods.requestDeposit(assetMint.parseAmount(1000)).accounts(cmmAccounts).rpc()
+    await user.program.methods.cancelDepositRequest().accounts(cmmAccounts).rpc()
+
+    // 990 reach the vault, 980.1 come back
+    expect(before - assetMint.balanceOf(user.publicKey)).to.equal(assetMint.parseAmountBigInt(19.9))
+    expect(assetMint.balanceOf(sharesMint.publicKey)).to.equal(0n)
+  })
+
+  it('records strategy debt net of the fee', async () => {
+    const { program, admin, users, assetMint } = fixture
+    for (const permission of [PERMISSIONS.STRATEGY_MANAGER, PERMISSIONS.DEBT_MANAGER]) {
+      await program.methods.grantPermission(permission).accounts({ to: admin.publicKey }).rpc()
+    }
+    const mock = await createMockStrategy(fixture)
+    await users[0].program.methods.deposit(assetMint.parseAmount(1000)).accounts(users[0].accounts).rpc()
+    await mock.add(assetMint.parseAmount(1000))
+    await mock.updateDebt(assetMint.parseAmount(600))
+
+    const strategy = await program.account.strategy.fetch(mock.strategy)
+    expect(strategy.currentDebt.toString()).to.equal(assetMint.parseAmount(594).toString())
+    expect(mock.balance()).to.equal(assetMint.parseAmountBigInt(594))
+  })
+})
@@ -0,0 +1,113 @@
+import { expect } from 'chai'
+import { createTransferCheckedInstruction } from '@solana/spl-token'
+import { AccountMeta, PublicKey, Transaction } from '@solana/web3.js'
+import { createFixture, Fixture, UserFixture } from './fixtures/tokenized-vault-fixture'
+import { nextSlot } from './fixtures/utils'
+import { ADDRESSES, INIT_OPTIONS, PERMISSIONS } from '../shared/tokenized-vault.constants'
+
+describe('shares transfer hook', () => {
+  let fixture: Fixture
+
+  beforeEach(async () => {
+    fixture = await createFixture({ initOptions: INIT_OPTIONS.SHARES_TRANSFER_HOOK })
+    const { program, users, assetMint } = fixture
+    for (const user of users.slice(0, 2)) {
+      await program.methods.grantPermission(PERMISSIONS.WHITELISTED).accounts({ to: user.publicKey }).rpc()
+    }
+    await users[0].program.methods.deposit(assetMint.parseAmount(10)).accounts(users[0].accounts).rpc()
+  })
+
+  // Extra accounts Token-2022 resolves from the hook's extra-account-metas list
+  const hookAccounts = (sourceOwner: PublicKey, destinationOwner: PublicKey): AccountMeta[] =>
+    [
+      fixture.program.programId,
+      ADDRESSES.ACCESS(sourceOwner),
+      ADDRESSES.ACCESS(destinationOwner),
+      fixture.hookProgram.programId,
+      ADDRESSES.EXTRA_ACCOUNT_METAS(ADDRESSES.SHARES_MINT),
+    ].map((pubkey) => ({ pubkey, isSigner: false, isWritable: false }))
+
+  const transferShares = async (from: UserFixture, to: UserFixture, amount: bigint) => {
+    const { sharesMint } = fixture
+    await sharesMint.createAtaOf(to.publicKey)
+    const ix = createTransferCheckedInstruction(
+      sharesMint.getAtaOf(from.publicKey),
+      sharesMint.publicKey,
+      sharesMint.getAtaOf(to.publicKey),
+      from.publicKey,
+      amount,
+      9,
+      [],
+      sharesMint.tokenProgramId
+    )
+    ix.keys.push(...hookAccounts(from.publicKey, to.publicKey))
+    await from.provider.send(new Transaction().add(ix))
+  }
+
+  it('allows transfers between whitelisted holders', async () => {
+    const { users, sharesMint } = fixture
+    const [alice, bob] = users
+    await transferShares(alice, bob, sharesMint.parseAmountBigInt(4))
+    expect(sharesMint.balanceOf(bob.publicKey)).to.equal(sharesMint.parseAmountBigInt(4))
+  })
+
+  it('rejects transfers to holders that are not whitelisted', async () => {
+    const { users, sharesMint } = fixture
+    const [alice, , carol] = users
+    await expect(transferShares(alice, carol, sharesMint.parseAmountBigInt(4))).to.be.rejectedWith(
+      'UnauthorizedPermission'
+    )
+  })
+
+  it('moves shares through the redeem escrow', async () => {
+    const { provider, users, sharesMint, cmmAccounts } = fixture
+    const [alice] = users
+    nextSlot(provider)
+    await alice.program.methods
+      .requestRedeem(sharesMint.parseAmount(4))
+      .accounts(cmmAccounts)
+      .remainingAccounts(hookAccounts(alice.publicKey, ADDRESSES.SHARES_MINT))
+      .rpc()
+    expect(sharesMint.balanceOfTokenAccount(ADDRESSES.REDEEM_ESCROW)).to.equal(sharesMint.parseAmountBigInt(4))
+
+    await alice.program.methods
+      .cancelRedeemRequest()
+      .accounts(cmmAccounts)
+      .remainingAccounts(hookAccounts(ADDRESSES.SHARES_MINT, alice.publicKey))
+      .rpc()
+    expect(sharesMint.balanceOf(alice.publicKey)).to.equal(sharesMint.parseAmountBigInt(10))
+  })
+
+  it('rejects redeem requests of holders that are not whitelisted', async () => {
+    const { provider, users, assetMint, sharesMint, cmmAccounts } = fixture
+    const carol = users[2]
+    await carol.program.methods.deposit(assetMint.parseAmount(10)).accounts(carol.accounts).rpc()
+    nextSlot(provider)
+    await expect(
+      carol.program.methods
+        .requestRedeem(sharesMint.parseAmount(4))
+        .accounts(cmmAccounts)
+        .remainingAccounts(hookAccounts(carol.publicKey, ADDRESSES.SHARES_MINT))
+        .rpc()
+    ).to.be.rejectedWith('UnauthorizedPermission')
+  })
+
+  it('delivers claimed async deposits', async () => {
+    const { program, admin, users, assetMint, sharesMint, cmmAccounts } = fixture
+    const [, bob] = users
+    await program.methods.grantPermission(PERMISSIONS.KEEPER).accounts({ to: admin.publicKey }).rpc()
+    await program.methods.setAsyncDeposits(true).rpc()
+    await bob.program.methods.requestDeposit(assetMint.parseAmount(10)).accounts(cmmAccounts).rpc()
+    await admin.program.methods
+      .settleDeposits()
+      .accounts({ ...cmmAccounts, access: ADDRESSES.ACCESS(admin.publicKey), depositEpoch: ADDRESSES.DEPOSIT_EPOCH(0) })
+      .rpc()
+
+    await bob.program.methods
+      .claimDeposit()
+      .accounts({ depositEpoch: ADDRESSES.DEPOSIT_EPOCH(0) })
+      .remainingAccounts(hookAccounts(ADDRESSES.SHARES_MINT, bob.publicKey))
+      .rpc()
+    expect(sharesMint.balanceOf(bob.publicKey)).to.equal(sharesMint.parseAmountBigInt(10))
+  })
+})